@@ -1,8 +1,12 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 use graphalgorithms_feedback_arc_set::{
   fas::{
+    bubble_sort::BubbleSort, chanas_kobylanski::ChanasKobylanski,
     divide_and_conquer_by_order_heuristic::DivideAndConquerByOrderHeuristic,
-    feedback_arc_set::FeedbackArcSet, greedy::GreedyHeuristic, simple_heuristic::SimpleHeuristic,
+    feedback_arc_set::FeedbackArcSet, greedy::GreedyHeuristic, kwik_sort::KwikSort,
+    multilevel_heuristic::MultilevelHeuristic, page_rank::PageRankHeuristic,
+    simple_heuristic::SimpleHeuristic, simulated_annealing::SimulatedAnnealing, sort_fas::SortFas,
+    spectral::SpectralHeuristic, tabu_search::TabuSearch,
   },
   tools::graphs::graph_from_file,
 };
@@ -18,7 +22,16 @@ pub fn file_benchmarks(c: &mut Criterion) {
     [
       SimpleHeuristic,
       GreedyHeuristic,
-      DivideAndConquerByOrderHeuristic
+      DivideAndConquerByOrderHeuristic,
+      MultilevelHeuristic,
+      SortFas,
+      ChanasKobylanski,
+      BubbleSort,
+      KwikSort,
+      PageRankHeuristic,
+      SpectralHeuristic,
+      SimulatedAnnealing,
+      TabuSearch
     ]
   );
 }
@@ -49,9 +62,7 @@ macro_rules! generate_benchmarks {
   (@call $bencher:expr, $graph:expr, $file_name:ident, [$($algo:ident),*]) => {
     paste::paste! {
       $(
-        let algo = $algo {
-          graph: &$graph,
-        };
+        let algo = $algo::new(&$graph);
         $bencher.bench_function(stringify!([<$algo _$file_name>]), |b| {
           b.iter(|| criterion::black_box(algo.feedback_arc_set()))
         });
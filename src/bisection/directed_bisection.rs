@@ -0,0 +1,8 @@
+use crate::graph::hash_table::{HashTable, VertexId};
+use std::collections::HashSet;
+
+/// Splits the vertices of a digraph into two parts V1 and V2 of about equal size,
+/// so that few arcs point backwards from V2 to V1.
+pub trait Bisection {
+  fn bisection(&self, graph: &HashTable) -> (HashSet<VertexId>, HashSet<VertexId>);
+}
@@ -0,0 +1,314 @@
+/*
+Fiduccia–Mattheyses refinement, adapted to minimize the arcs from V2 to V1:
+
+repeat
+    unlock all vertices; put every vertex into the gain bucket of its part
+    while there is an unlocked vertex whose move keeps the balance
+        i := unlocked vertex of maximum gain(i), whose move keeps the balance
+        move i into the other part and lock it
+        update the gains of the unlocked neighbors of i
+        remember the bisection, if its cost is the smallest of this pass
+    undo all moves after the best bisection of this pass
+until a pass does not reduce the cost
+
+The gain buckets allow to find and update the vertex of maximum gain without scanning all vertices,
+so a pass takes O(|E| log |V|).
+ */
+
+use crate::bisection::directed_bisection::Bisection;
+use crate::bisection::weighted_digraph::{balanced_split, Partition, WeightedDigraph};
+use crate::graph::hash_table::{HashTable, VertexId};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::collections::{BTreeMap, HashSet};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parameters {
+  /// Balance: No partition weighs more than alpha * (weight of V), in [0.5, 1]
+  pub alpha: f32,
+  /// Upper bound for the number of passes
+  pub max_passes: usize,
+  /// The same seed always gives the same random initial bisection
+  pub seed: u64,
+}
+
+impl Default for Parameters {
+  fn default() -> Self {
+    Self {
+      alpha: 0.55,
+      max_passes: 20,
+      seed: 0,
+    }
+  }
+}
+
+impl Parameters {
+  /// The default parameters with another seed
+  pub fn with_seed(seed: u64) -> Self {
+    Self {
+      seed,
+      ..Self::default()
+    }
+  }
+}
+
+#[derive(Default)]
+pub struct FiducciaMattheyses {
+  parameters: Parameters,
+}
+
+impl FiducciaMattheyses {
+  /// Panics if alpha is not in [0.5, 1]
+  pub fn new(parameters: Parameters) -> Self {
+    assert!(
+      parameters.alpha >= 0.5 && parameters.alpha <= 1.0,
+      "alpha must be in [0.5, 1]"
+    );
+    Self { parameters }
+  }
+}
+
+impl Bisection for FiducciaMattheyses {
+  fn bisection(&self, graph: &HashTable) -> (HashSet<VertexId>, HashSet<VertexId>) {
+    let (digraph, vertices) = WeightedDigraph::from_graph(graph);
+
+    let mut order = (0..digraph.order()).collect::<Vec<_>>();
+    order.shuffle(&mut StdRng::seed_from_u64(self.parameters.seed));
+    let mut partition = Partition::new(&digraph, &balanced_split(&digraph, &order));
+
+    let max_part_weight = max_part_weight(&digraph, self.parameters.alpha);
+    refine(&mut partition, max_part_weight, self.parameters.max_passes);
+
+    let (v_2, v_1) = vertices
+      .into_iter()
+      .enumerate()
+      .partition::<Vec<_>, _>(|(i, _)| partition.in_v_2[*i]);
+    (
+      v_1.into_iter().map(|(_, v)| v).collect(),
+      v_2.into_iter().map(|(_, v)| v).collect(),
+    )
+  }
+}
+
+/// Largest weight a part may have. It is at least as large as the heavier part of the most even split.
+pub(crate) fn max_part_weight(graph: &WeightedDigraph, alpha: f32) -> i64 {
+  let total = graph.total_vertex_weight();
+  let heaviest = graph.vertex_weights.iter().copied().max().unwrap_or(0);
+  ((alpha as f64 * total as f64) as i64).max((total + 1) / 2 + heaviest)
+}
+
+/// Improves the partition by passes of FM until the cost no longer decreases.
+/// Moves, which would make a part heavier than max_part_weight, are not executed.
+pub(crate) fn refine(partition: &mut Partition, max_part_weight: i64, max_passes: usize) {
+  for _ in 0..max_passes {
+    if !pass(partition, max_part_weight) {
+      break;
+    }
+  }
+}
+
+// Returns whether the pass reduced the cost
+fn pass(partition: &mut Partition, max_part_weight: i64) -> bool {
+  let graph = partition.graph;
+  let n = graph.order();
+  let mut locked = vec![false; n];
+  // One bucket structure per part, indexed by in_v_2
+  let mut buckets = [GainBuckets::new(n), GainBuckets::new(n)];
+  for i in 0..n {
+    buckets[partition.in_v_2[i] as usize].insert(i, partition.gain(i));
+  }
+
+  let initial_cost = partition.cost;
+  let mut best_cost = initial_cost;
+  let mut moves = vec![];
+  let mut best_moves = 0;
+
+  loop {
+    // The best vertex of each part, if its move keeps the balance
+    let candidate = [false, true]
+      .into_iter()
+      .filter_map(|in_v_2| {
+        let i = buckets[in_v_2 as usize].max()?;
+        let target_weight = partition.weight_of_part(!in_v_2) + graph.vertex_weights[i];
+        (target_weight <= max_part_weight).then_some(i)
+      })
+      .max_by_key(|i| {
+        (
+          partition.gain(*i),
+          partition.weight_of_part(partition.in_v_2[*i]),
+        )
+      });
+    let Some(i) = candidate else {
+      break;
+    };
+
+    buckets[partition.in_v_2[i] as usize].remove(i);
+    locked[i] = true;
+    partition.move_vertex(i);
+    moves.push(i);
+
+    for &(j, _) in graph.successors[i].iter().chain(&graph.predecessors[i]) {
+      if !locked[j] {
+        buckets[partition.in_v_2[j] as usize].update(j, partition.gain(j));
+      }
+    }
+
+    if partition.cost < best_cost {
+      best_cost = partition.cost;
+      best_moves = moves.len();
+    }
+  }
+
+  for &i in moves[best_moves..].iter().rev() {
+    partition.move_vertex(i);
+  }
+  debug_assert_eq!(partition.cost, best_cost);
+  best_cost < initial_cost
+}
+
+/*
+Vertices grouped by their gain. Vertices with the same gain are kept in a Vec together with
+their position in it, so insert, remove and update take O(log #gains).
+ */
+pub(crate) struct GainBuckets {
+  buckets: BTreeMap<i64, Vec<usize>>,
+  gain: Vec<i64>,
+  position: Vec<Option<usize>>,
+}
+
+impl GainBuckets {
+  pub(crate) fn new(n: usize) -> Self {
+    Self {
+      buckets: BTreeMap::new(),
+      gain: vec![0; n],
+      position: vec![None; n],
+    }
+  }
+
+  pub(crate) fn insert(&mut self, i: usize, gain: i64) {
+    debug_assert!(self.position[i].is_none());
+    let bucket = self.buckets.entry(gain).or_default();
+    self.position[i] = Some(bucket.len());
+    self.gain[i] = gain;
+    bucket.push(i);
+  }
+
+  pub(crate) fn remove(&mut self, i: usize) {
+    let position = self.position[i].take().expect("vertex is not in a bucket");
+    let bucket = self.buckets.get_mut(&self.gain[i]).unwrap();
+    bucket.swap_remove(position);
+    if let Some(&moved) = bucket.get(position) {
+      self.position[moved] = Some(position);
+    }
+    if bucket.is_empty() {
+      self.buckets.remove(&self.gain[i]);
+    }
+  }
+
+  pub(crate) fn update(&mut self, i: usize, gain: i64) {
+    if self.gain[i] != gain {
+      self.remove(i);
+      self.insert(i, gain);
+    }
+  }
+
+  // Vertex with the maximum gain
+  pub(crate) fn max(&self) -> Option<usize> {
+    self
+      .buckets
+      .last_key_value()
+      .and_then(|(_, bucket)| bucket.last().copied())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::bisection::directed_bisection::Bisection;
+  use crate::bisection::fiduccia_mattheyses::{
+    max_part_weight, refine, FiducciaMattheyses, GainBuckets, Parameters,
+  };
+  use crate::bisection::weighted_digraph::{Partition, WeightedDigraph};
+  use crate::tools::graphs::{graph_from_file, graph_from_wikipedia_scc};
+
+  #[test]
+  fn gain_buckets_work() {
+    let mut buckets = GainBuckets::new(4);
+    buckets.insert(0, 1);
+    buckets.insert(1, 3);
+    buckets.insert(2, 3);
+    buckets.insert(3, -2);
+
+    assert_eq!(buckets.max(), Some(2));
+    buckets.remove(2);
+    assert_eq!(buckets.max(), Some(1));
+    buckets.update(1, 0);
+    assert_eq!(buckets.max(), Some(0));
+    buckets.remove(0);
+    buckets.remove(1);
+    assert_eq!(buckets.max(), Some(3));
+    buckets.remove(3);
+    assert_eq!(buckets.max(), None);
+  }
+
+  #[test]
+  fn finds_optimal_bisection_of_two_cycles() {
+    // Two 2-cycles {0, 1} and {2, 3}, connected by one arc 1 → 2
+    let mut digraph = WeightedDigraph::with_vertex_weights(vec![1; 4]);
+    for (u, v) in [(0, 1), (1, 0), (2, 3), (3, 2), (1, 2)] {
+      digraph.add_arc(u, v, 1);
+    }
+    let mut partition = Partition::new(&digraph, &[false, true, false, true]);
+    refine(&mut partition, max_part_weight(&digraph, 0.5), 10);
+
+    assert_eq!(partition.cost, 0);
+    assert_eq!(partition.in_v_2, vec![false, false, true, true]);
+  }
+
+  #[test]
+  fn respects_balance() {
+    let graph = graph_from_file("h_001");
+    let (v_1, v_2) = FiducciaMattheyses::default().bisection(&graph);
+
+    assert_eq!(v_1.len() + v_2.len(), graph.order());
+    assert!(v_1.len() as f32 <= 0.55 * graph.order() as f32 + 1.0);
+    assert!(v_2.len() as f32 <= 0.55 * graph.order() as f32 + 1.0);
+  }
+
+  #[test]
+  fn improves_random_bisection() {
+    let graph = graph_from_file("h_001");
+    let (digraph, _) = WeightedDigraph::from_graph(&graph);
+    let in_v_2 = (0..digraph.order()).map(|i| i % 2 == 1).collect::<Vec<_>>();
+    let mut partition = Partition::new(&digraph, &in_v_2);
+    let initial_cost = partition.cost;
+
+    refine(&mut partition, max_part_weight(&digraph, 0.55), 20);
+
+    assert!(partition.cost < initial_cost);
+    assert_eq!(partition.cost, partition.recompute_cost());
+  }
+
+  #[test]
+  fn same_seed_gives_same_bisection() {
+    let graph = graph_from_wikipedia_scc();
+    let parameters = Parameters {
+      seed: 7,
+      ..Parameters::default()
+    };
+
+    assert_eq!(
+      FiducciaMattheyses::new(parameters).bisection(&graph),
+      FiducciaMattheyses::new(parameters).bisection(&graph)
+    );
+  }
+
+  #[test]
+  #[should_panic(expected = "alpha must be in [0.5, 1]")]
+  fn rejects_unbalanced_alpha() {
+    FiducciaMattheyses::new(Parameters {
+      alpha: 1.5,
+      ..Parameters::default()
+    });
+  }
+}
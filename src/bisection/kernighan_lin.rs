@@ -0,0 +1,211 @@
+/*
+Kernighan–Lin refinement, adapted to minimize the arcs from V2 to V1. Instead of moving single
+vertices it swaps pairs, so both parts keep their size:
+
+repeat
+    unlock all vertices
+    while both parts contain an unlocked vertex
+        (a, b) := unlocked a ∈ V1 and b ∈ V2 of maximum gain(a) + gain(b) - w(a → b) - w(b → a)
+        swap a and b and lock them
+        remember the bisection, if its cost is the smallest of this pass
+    undo all swaps after the best bisection of this pass
+until a pass does not reduce the cost
+
+The arcs between a and b are subtracted, because gain(a) and gain(b) both assume that the other
+vertex stays. The candidates are sorted by gain, so the search for the best pair can stop as soon as
+gain(a) + gain(b) is not larger than the best pair found. Still, a pass takes O(|V|² log |V|).
+ */
+
+use crate::bisection::directed_bisection::Bisection;
+use crate::bisection::weighted_digraph::{balanced_split, Partition, WeightedDigraph};
+use crate::graph::hash_table::{HashTable, VertexId};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parameters {
+  /// Upper bound for the number of passes
+  pub max_passes: usize,
+  /// The same seed always gives the same random initial bisection
+  pub seed: u64,
+}
+
+impl Default for Parameters {
+  fn default() -> Self {
+    Self {
+      max_passes: 10,
+      seed: 0,
+    }
+  }
+}
+
+impl Parameters {
+  /// The default parameters with another seed
+  pub fn with_seed(seed: u64) -> Self {
+    Self {
+      seed,
+      ..Self::default()
+    }
+  }
+}
+
+#[derive(Default)]
+pub struct KernighanLin {
+  parameters: Parameters,
+}
+
+impl KernighanLin {
+  pub fn new(parameters: Parameters) -> Self {
+    Self { parameters }
+  }
+}
+
+impl Bisection for KernighanLin {
+  fn bisection(&self, graph: &HashTable) -> (HashSet<VertexId>, HashSet<VertexId>) {
+    let (digraph, vertices) = WeightedDigraph::from_graph(graph);
+
+    let mut order = (0..digraph.order()).collect::<Vec<_>>();
+    order.shuffle(&mut StdRng::seed_from_u64(self.parameters.seed));
+    let mut partition = Partition::new(&digraph, &balanced_split(&digraph, &order));
+
+    for _ in 0..self.parameters.max_passes {
+      if !pass(&mut partition) {
+        break;
+      }
+    }
+
+    let (v_2, v_1) = vertices
+      .into_iter()
+      .enumerate()
+      .partition::<Vec<_>, _>(|(i, _)| partition.in_v_2[*i]);
+    (
+      v_1.into_iter().map(|(_, v)| v).collect(),
+      v_2.into_iter().map(|(_, v)| v).collect(),
+    )
+  }
+}
+
+// Returns whether the pass reduced the cost
+fn pass(partition: &mut Partition) -> bool {
+  let graph = partition.graph;
+  let n = graph.order();
+  let mut locked = vec![false; n];
+
+  let initial_cost = partition.cost;
+  let mut best_cost = initial_cost;
+  let mut swaps = vec![];
+  let mut best_swaps = 0;
+
+  loop {
+    let mut candidates = [vec![], vec![]];
+    for i in (0..n).filter(|i| !locked[*i]) {
+      candidates[partition.in_v_2[i] as usize].push((partition.gain(i), i));
+    }
+    if candidates.iter().any(|c| c.is_empty()) {
+      break;
+    }
+    candidates
+      .iter_mut()
+      .for_each(|c| c.sort_unstable_by(|x, y| y.cmp(x)));
+
+    let mut best_pair = None;
+    let mut best_gain = i64::MIN;
+    'search: for &(gain_a, a) in &candidates[0] {
+      for &(gain_b, b) in &candidates[1] {
+        if gain_a + gain_b <= best_gain {
+          if b == candidates[1][0].1 {
+            break 'search;
+          }
+          break;
+        }
+        let gain = gain_a + gain_b - graph.arc_weight(a, b) - graph.arc_weight(b, a);
+        if gain > best_gain {
+          best_gain = gain;
+          best_pair = Some((a, b));
+        }
+      }
+    }
+
+    let (a, b) = best_pair.unwrap();
+    partition.move_vertex(a);
+    partition.move_vertex(b);
+    locked[a] = true;
+    locked[b] = true;
+    swaps.push((a, b));
+
+    if partition.cost < best_cost {
+      best_cost = partition.cost;
+      best_swaps = swaps.len();
+    }
+  }
+
+  for &(a, b) in swaps[best_swaps..].iter().rev() {
+    partition.move_vertex(b);
+    partition.move_vertex(a);
+  }
+  debug_assert_eq!(partition.cost, best_cost);
+  best_cost < initial_cost
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::bisection::directed_bisection::Bisection;
+  use crate::bisection::kernighan_lin::{pass, KernighanLin, Parameters};
+  use crate::bisection::weighted_digraph::{Partition, WeightedDigraph};
+  use crate::tools::graphs::{graph_from_file, graph_from_wikipedia_scc};
+
+  #[test]
+  fn swap_gain_accounts_for_arcs_between_pair() {
+    // A 2-cycle 0 ⇄ 1 and a path 1 → 2 → 3, vertex 0 and 2 in V1
+    let mut digraph = WeightedDigraph::with_vertex_weights(vec![1; 4]);
+    for (u, v) in [(0, 1), (1, 0), (1, 2), (2, 3)] {
+      digraph.add_arc(u, v, 1);
+    }
+    let mut partition = Partition::new(&digraph, &[false, true, false, true]);
+    assert_eq!(partition.cost, 2);
+
+    // Swapping 2 and 1 has gain 1 + 2 - w(1 → 2) = 2
+    assert!(pass(&mut partition));
+    assert_eq!(partition.cost, 0);
+    assert_eq!(partition.cost, partition.recompute_cost());
+    assert_eq!(partition.in_v_2, vec![false, false, true, true]);
+  }
+
+  #[test]
+  fn keeps_parts_equal_sized() {
+    let graph = graph_from_file("h_001");
+    let (v_1, v_2) = KernighanLin::default().bisection(&graph);
+
+    assert_eq!(v_1.len(), graph.order() / 2);
+    assert_eq!(v_2.len(), graph.order() - graph.order() / 2);
+  }
+
+  #[test]
+  fn improves_random_bisection() {
+    let graph = graph_from_file("h_001");
+    let (digraph, _) = WeightedDigraph::from_graph(&graph);
+    let in_v_2 = (0..digraph.order()).map(|i| i % 2 == 1).collect::<Vec<_>>();
+    let mut partition = Partition::new(&digraph, &in_v_2);
+    let initial_cost = partition.cost;
+
+    assert!(pass(&mut partition));
+    assert!(partition.cost < initial_cost);
+    assert_eq!(partition.cost, partition.recompute_cost());
+  }
+
+  #[test]
+  fn same_seed_gives_same_bisection() {
+    let graph = graph_from_wikipedia_scc();
+    let parameters = Parameters {
+      seed: 7,
+      ..Parameters::default()
+    };
+
+    assert_eq!(
+      KernighanLin::new(parameters).bisection(&graph),
+      KernighanLin::new(parameters).bisection(&graph)
+    );
+  }
+}
@@ -1 +1,6 @@
+pub mod directed_bisection;
+pub mod fiduccia_mattheyses;
+pub mod kernighan_lin;
+pub mod multilevel;
 pub mod stochastic_evolution;
+pub(crate) mod weighted_digraph;
@@ -0,0 +1,296 @@
+/*
+Multilevel bisection in the style of METIS, minimizing the arcs from V2 to V1:
+
+G0 := G
+while Gi has more than coarsest_order vertices
+    match heavily connected vertex pairs of Gi
+    Gi+1 := Gi with every matched pair contracted to a single vertex (vertex and arc weights are added up)
+(V1, V2) := best of several random bisections of the coarsest graph, each refined by Fiduccia–Mattheyses
+for every level i from coarsest to finest
+    project (V1, V2) onto Gi
+    refine (V1, V2) by Fiduccia–Mattheyses on Gi
+
+Coarsening stops early, if the matching hardly reduces the number of vertices. Refining on the coarse
+levels moves whole clusters at once, which a flat local search can not do.
+ */
+
+use crate::bisection::directed_bisection::Bisection;
+use crate::bisection::fiduccia_mattheyses::{max_part_weight, refine, GainBuckets};
+use crate::bisection::weighted_digraph::{
+  balanced_split, coarsen, Level, Partition, WeightedDigraph,
+};
+use crate::graph::hash_table::{HashTable, VertexId};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parameters {
+  /// Coarsening stops as soon as the graph has at most this many vertices, at least 2
+  pub coarsest_order: usize,
+  /// Number of random bisections of the coarsest graph, the best one is projected back, at least 1
+  pub initial_tries: usize,
+  /// Balance: No partition weighs more than alpha * (weight of V), in [0.5, 1]
+  pub alpha: f32,
+  /// Upper bound for the number of Fiduccia–Mattheyses passes on each level
+  pub max_passes: usize,
+  /// The same seed always gives the same bisection
+  pub seed: u64,
+}
+
+impl Default for Parameters {
+  fn default() -> Self {
+    Self {
+      coarsest_order: 64,
+      initial_tries: 8,
+      alpha: 0.55,
+      max_passes: 10,
+      seed: 0,
+    }
+  }
+}
+
+impl Parameters {
+  /// The default parameters with another seed
+  pub fn with_seed(seed: u64) -> Self {
+    Self {
+      seed,
+      ..Self::default()
+    }
+  }
+}
+
+#[derive(Default)]
+pub struct Multilevel {
+  parameters: Parameters,
+}
+
+impl Multilevel {
+  /// Panics if the parameters are out of the ranges documented in Parameters
+  pub fn new(parameters: Parameters) -> Self {
+    assert!(
+      parameters.coarsest_order >= 2,
+      "coarsest_order must be at least 2"
+    );
+    assert!(
+      parameters.initial_tries > 0,
+      "initial_tries must be positive"
+    );
+    assert!(
+      parameters.alpha >= 0.5 && parameters.alpha <= 1.0,
+      "alpha must be in [0.5, 1]"
+    );
+    Self { parameters }
+  }
+
+  fn coarsen(&self, graph: &WeightedDigraph, rng: &mut StdRng) -> Vec<Level> {
+    let max_vertex_weight =
+      (3 * graph.total_vertex_weight() / (2 * self.parameters.coarsest_order as i64)).max(2);
+    coarsen(
+      graph,
+      self.parameters.coarsest_order,
+      rng,
+      |finer, order| finer.heavy_matching(order, max_vertex_weight),
+    )
+  }
+
+  // Best of several random bisections of the graph, each refined by Fiduccia–Mattheyses
+  fn initial_bisection(&self, graph: &WeightedDigraph, rng: &mut StdRng) -> Vec<bool> {
+    let max_part_weight = max_part_weight(graph, self.parameters.alpha);
+    let mut order = (0..graph.order()).collect::<Vec<_>>();
+
+    (0..self.parameters.initial_tries)
+      .map(|_| {
+        order.shuffle(rng);
+        let mut partition = Partition::new(graph, &balanced_split(graph, &order));
+        refine(&mut partition, max_part_weight, self.parameters.max_passes);
+        (partition.cost, partition.in_v_2)
+      })
+      .min_by_key(|(cost, _)| *cost)
+      .map(|(_, in_v_2)| in_v_2)
+      .unwrap()
+  }
+}
+
+impl Bisection for Multilevel {
+  fn bisection(&self, graph: &HashTable) -> (HashSet<VertexId>, HashSet<VertexId>) {
+    let (digraph, vertices) = WeightedDigraph::from_graph(graph);
+    let mut rng = StdRng::seed_from_u64(self.parameters.seed);
+
+    let levels = self.coarsen(&digraph, &mut rng);
+    let coarsest = levels.last().map_or(&digraph, |level| &level.graph);
+    let mut in_v_2 = self.initial_bisection(coarsest, &mut rng);
+
+    for i in (0..levels.len()).rev() {
+      let finer = if i == 0 {
+        &digraph
+      } else {
+        &levels[i - 1].graph
+      };
+      let projected = levels[i]
+        .coarse
+        .iter()
+        .map(|c| in_v_2[*c])
+        .collect::<Vec<_>>();
+
+      let max_part_weight = max_part_weight(finer, self.parameters.alpha);
+      let mut partition = Partition::new(finer, &projected);
+      rebalance(&mut partition, max_part_weight);
+      refine(&mut partition, max_part_weight, self.parameters.max_passes);
+      in_v_2 = partition.in_v_2;
+    }
+
+    let (v_2, v_1) = vertices
+      .into_iter()
+      .enumerate()
+      .partition::<Vec<_>, _>(|(i, _)| in_v_2[*i]);
+    (
+      v_1.into_iter().map(|(_, v)| v).collect(),
+      v_2.into_iter().map(|(_, v)| v).collect(),
+    )
+  }
+}
+
+// A projected bisection may be too unbalanced for the finer graph, because its heaviest vertex is lighter.
+// Then the vertices of maximum gain are moved out of the heavier part, found in gain buckets as in FM.
+fn rebalance(partition: &mut Partition, max_part_weight: i64) {
+  let heavier_is_v_2 = partition.weight_v_2 > partition.weight_v_1();
+  if partition.weight_of_part(heavier_is_v_2) <= max_part_weight {
+    return;
+  }
+
+  let graph = partition.graph;
+  let mut buckets = GainBuckets::new(graph.order());
+  for i in (0..graph.order()).filter(|i| partition.in_v_2[*i] == heavier_is_v_2) {
+    buckets.insert(i, partition.gain(i));
+  }
+  // Moving a vertex out can not make the lighter part too heavy, see max_part_weight
+  while partition.weight_of_part(heavier_is_v_2) > max_part_weight {
+    let i = buckets.max().unwrap();
+    buckets.remove(i);
+    partition.move_vertex(i);
+    for &(j, _) in graph.successors[i].iter().chain(&graph.predecessors[i]) {
+      if partition.in_v_2[j] == heavier_is_v_2 {
+        buckets.update(j, partition.gain(j));
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::bisection::directed_bisection::Bisection;
+  use crate::bisection::fiduccia_mattheyses::FiducciaMattheyses;
+  use crate::bisection::multilevel::{rebalance, Multilevel, Parameters};
+  use crate::bisection::weighted_digraph::{Partition, WeightedDigraph};
+  use crate::tools::graphs::{graph_from_file, graph_from_wikipedia_scc};
+  use rand::rngs::StdRng;
+  use rand::SeedableRng;
+
+  #[test]
+  fn coarsens_until_coarsest_order() {
+    let (digraph, _) = WeightedDigraph::from_graph(&graph_from_file("h_001"));
+    let levels = Multilevel::default().coarsen(&digraph, &mut StdRng::seed_from_u64(0));
+
+    assert!(!levels.is_empty());
+    let mut finer = &digraph;
+    for level in &levels {
+      assert_eq!(level.coarse.len(), finer.order());
+      assert!(level.graph.order() < finer.order());
+      assert_eq!(
+        level.graph.total_vertex_weight(),
+        digraph.total_vertex_weight()
+      );
+      finer = &level.graph;
+    }
+  }
+
+  #[test]
+  fn respects_balance() {
+    let graph = graph_from_file("h_001");
+    let (v_1, v_2) = Multilevel::default().bisection(&graph);
+
+    assert_eq!(v_1.len() + v_2.len(), graph.order());
+    assert!(v_1.len() as f32 <= 0.55 * graph.order() as f32 + 1.0);
+    assert!(v_2.len() as f32 <= 0.55 * graph.order() as f32 + 1.0);
+  }
+
+  #[test]
+  fn is_not_worse_than_flat_fiduccia_mattheyses() {
+    let graph = graph_from_file("h_001");
+    let cost = |(v_1, v_2)| graph.edges_from_to(&v_2, &v_1).len();
+
+    let multilevel = cost(Multilevel::default().bisection(&graph));
+    let flat = cost(FiducciaMattheyses::default().bisection(&graph));
+    assert!(multilevel <= flat, "{multilevel} > {flat}");
+  }
+
+  #[test]
+  fn rebalance_works() {
+    let digraph = WeightedDigraph::with_vertex_weights(vec![1; 6]);
+    let mut partition = Partition::new(&digraph, &[false, false, false, false, false, true]);
+
+    rebalance(&mut partition, 4);
+    assert_eq!(partition.weight_v_1(), 4);
+    assert_eq!(partition.weight_v_2, 2);
+  }
+
+  #[test]
+  fn rebalance_moves_vertices_of_maximum_gain() {
+    // V2 = {5}: moving 2 or 3 into V2 removes an arc 5 → 2 or 5 → 3, moving 1 would add 1 → 4
+    let mut digraph = WeightedDigraph::with_vertex_weights(vec![1; 6]);
+    for (u, v) in [(5, 2), (5, 3), (1, 4)] {
+      digraph.add_arc(u, v, 1);
+    }
+    let mut partition = Partition::new(&digraph, &[false, false, false, false, false, true]);
+    assert_eq!(partition.cost, 2);
+
+    rebalance(&mut partition, 4);
+    assert_eq!(partition.weight_v_1(), 4);
+    assert_eq!(partition.cost, 1);
+    assert_eq!(partition.cost, partition.recompute_cost());
+  }
+
+  #[test]
+  fn same_seed_gives_same_bisection() {
+    let graph = graph_from_wikipedia_scc();
+    let parameters = Parameters {
+      coarsest_order: 2,
+      seed: 7,
+      ..Parameters::default()
+    };
+
+    assert_eq!(
+      Multilevel::new(parameters).bisection(&graph),
+      Multilevel::new(parameters).bisection(&graph)
+    );
+  }
+
+  #[test]
+  #[should_panic(expected = "coarsest_order must be at least 2")]
+  fn rejects_coarsest_order_below_two() {
+    Multilevel::new(Parameters {
+      coarsest_order: 0,
+      ..Parameters::default()
+    });
+  }
+
+  #[test]
+  #[should_panic(expected = "initial_tries must be positive")]
+  fn rejects_zero_initial_tries() {
+    Multilevel::new(Parameters {
+      initial_tries: 0,
+      ..Parameters::default()
+    });
+  }
+
+  #[test]
+  #[should_panic(expected = "alpha must be in [0.5, 1]")]
+  fn rejects_unbalanced_alpha() {
+    Multilevel::new(Parameters {
+      alpha: 0.4,
+      ..Parameters::default()
+    });
+  }
+}
@@ -25,195 +25,185 @@ until counter > R
 return (B1 , B2 ).
  */
 
-use crate::graph::hash_table::{Direction, HashTable, VertexId};
-use rand::Rng;
+use crate::bisection::directed_bisection::Bisection;
+use crate::bisection::weighted_digraph::{Partition, WeightedDigraph};
+use crate::graph::hash_table::{HashTable, VertexId};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use std::collections::HashSet;
 
-const ALPHA: f32 = 0.6;
-
-pub enum Bisection {
-  CurrentBisection,
-  BestBisection,
+/// Input parameters of the stochastic evolution, see the pseudocode above
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parameters {
+  /// Initial value of p, must be negative. A vertex i is moved by perturb if gain(i) > random(p..0)
+  pub p0: i32,
+  /// Iteration control R, must be greater than 1
+  pub r: i32,
+  /// Decrease δ of p if perturb did not change the cost, must be positive
+  pub delta: i32,
+  /// Balance: After perturb no partition contains more than alpha * |V| vertices
+  pub alpha: f32,
+  /// The same seed always gives the same bisection
+  pub seed: u64,
+  /// Upper bound for the number of calls to perturb
+  pub max_passes: usize,
 }
 
-pub struct StochasticEvolution<'a> {
-  graph: &'a HashTable,
-  current_bisection: (Vec<usize>, Vec<usize>),
-  best_bisection: (Vec<usize>, Vec<usize>),
-  vertices: Vec<VertexId>,
+impl Default for Parameters {
+  fn default() -> Self {
+    Self {
+      p0: -1,
+      r: 10,
+      delta: 2,
+      alpha: 0.6,
+      seed: 0,
+      max_passes: 10_000,
+    }
+  }
 }
 
-impl<'a> StochasticEvolution<'a> {
-  pub fn new(graph: &'a HashTable) -> Self {
-    let vertices = graph.vertices();
+impl Parameters {
+  /// The default parameters with another seed
+  pub fn with_seed(seed: u64) -> Self {
     Self {
-      graph,
-      current_bisection: initial_bisection(vertices.len()),
-      best_bisection: initial_bisection(vertices.len()),
-      vertices,
+      seed,
+      ..Self::default()
     }
   }
+}
+
+#[derive(Default)]
+pub struct StochasticEvolution {
+  parameters: Parameters,
+}
+
+impl StochasticEvolution {
+  /// Panics if the parameters are out of the ranges documented in Parameters
+  pub fn new(parameters: Parameters) -> Self {
+    assert!(parameters.p0 < 0, "p0 must be negative");
+    assert!(parameters.r > 1, "r must be greater than 1");
+    assert!(parameters.delta > 0, "delta must be positive");
+    assert!(
+      parameters.alpha >= 0.5 && parameters.alpha <= 1.0,
+      "alpha must be in [0.5, 1]"
+    );
+    Self { parameters }
+  }
+}
 
-  pub fn bisection(&mut self) -> (HashSet<VertexId>, HashSet<VertexId>) {
-    //Input Parameters:
-    let initial_p = -1;
-    let initial_r = 10;
-    let initial_delta = 2;
-    debug_assert!(initial_p <= 0);
-    debug_assert!(initial_r > 1);
-    debug_assert!(initial_delta > 0);
+impl Bisection for StochasticEvolution {
+  fn bisection(&self, graph: &HashTable) -> (HashSet<VertexId>, HashSet<VertexId>) {
+    let parameters = &self.parameters;
+    let (digraph, vertices) = WeightedDigraph::from_graph(graph);
+    let mut state = State::new(&digraph, parameters.seed);
+    let mut best_bisection = state.partition.in_v_2.clone();
+    let mut best_cost = state.partition.cost;
 
-    let mut p = initial_p;
+    let mut p = parameters.p0;
     let mut counter = 0;
 
-    loop {
-      let c_pre = self.cost(Bisection::BestBisection);
-      self.perturb(p);
-      let c_post = self.cost(Bisection::CurrentBisection);
+    for _ in 0..parameters.max_passes {
+      let c_pre = best_cost;
+      state.perturb(p, parameters.alpha);
+      let c_post = state.partition.cost;
       if c_post < c_pre {
-        self.best_bisection = self.current_bisection.clone();
-        counter -= initial_r;
+        best_bisection.clone_from(&state.partition.in_v_2);
+        best_cost = c_post;
+        counter -= parameters.r;
       } else {
         counter += 1;
       }
       if c_post == c_pre {
-        p -= initial_delta;
+        p = p.saturating_sub(parameters.delta);
       } else {
-        p = initial_p;
+        p = parameters.p0;
       }
-      if counter > initial_r {
+      if counter > parameters.r {
         break;
       }
     }
 
-    let bisection = |vec: &Vec<usize>| -> HashSet<VertexId> {
-      vec.iter().map(|idx| self.vertices[*idx]).collect()
+    let bisection = |in_v_2: bool| -> HashSet<VertexId> {
+      (0..vertices.len())
+        .filter(|i| best_bisection[*i] == in_v_2)
+        .map(|i| vertices[i])
+        .collect()
     };
-    (
-      bisection(&self.best_bisection.0),
-      bisection(&self.best_bisection.1),
-    )
+    (bisection(false), bisection(true))
   }
+}
 
-  fn perturb(&mut self, p: i32) {
-    let mut s1 = vec![];
-    let mut s2 = vec![];
-
-    for i in 0..self.vertices.len() {
-      if self.gain(i) > rand::thread_rng().gen_range(p..0) {
-        self.move_vertex(i);
-        if self.current_bisection.0.contains(&i) {
-          s1.push(i);
-        } else if self.current_bisection.1.contains(&i) {
-          s2.push(i);
-        } else {
-          panic!("No bisection containts vertex idx {}", i);
-        }
-      }
-    }
-
-    let mut bisection;
-    let mut stack;
-    let is_left_bisection;
-    if self.current_bisection.0.len() > self.current_bisection.1.len() {
-      bisection = &self.current_bisection.0;
-      stack = s1;
-      is_left_bisection = true;
-    } else {
-      bisection = &self.current_bisection.1;
-      stack = s2;
-      is_left_bisection = false;
-    }
+/*
+The current bisection (V1, V2) on vertex indices. The Partition keeps the cost and the gain of
+every vertex up to date, see weighted_digraph. Self-loops are not part of the WeightedDigraph,
+they never point from V2 to V1.
+ */
+struct State<'g> {
+  partition: Partition<'g>,
+  rng: StdRng,
+}
 
-    while bisection.len() as f32 > (ALPHA * self.vertices.len() as f32) {
-      let i = stack.pop().unwrap();
-      self.move_vertex(i);
-      if is_left_bisection {
-        bisection = &self.current_bisection.0;
-      } else {
-        bisection = &self.current_bisection.1;
-      }
+impl<'g> State<'g> {
+  // Initial bisection: first half of the vertices in V1, second half in V2
+  fn new(graph: &'g WeightedDigraph, seed: u64) -> Self {
+    let n = graph.order();
+    let in_v_2 = (0..n).map(|i| i >= n / 2).collect::<Vec<_>>();
+    Self {
+      partition: Partition::new(graph, &in_v_2),
+      rng: StdRng::seed_from_u64(seed),
     }
   }
 
-  // Number of Edges from Partition 2 to Partition 1
-  fn cost(&mut self, on: Bisection) -> usize {
-    let mut cost = 0;
-    let correct_bisection = match on {
-      Bisection::CurrentBisection => &self.current_bisection,
-      Bisection::BestBisection => &self.best_bisection,
-    };
+  fn perturb(&mut self, p: i32, alpha: f32) {
+    let partition = &mut self.partition;
+    let mut moved_to_v_1 = vec![];
+    let mut moved_to_v_2 = vec![];
 
-    let bisection_2 = &correct_bisection.1;
-    for v_2_index in bisection_2 {
-      let v_2 = self.vertices[*v_2_index];
-      for (_, neighbour) in self.graph.edges(v_2, Direction::Outbound) {
-        let neighbor_pos = self.vertices.iter().position(|v| *v == neighbour).unwrap();
-        // check if neighbor is in other partition
-        if correct_bisection.0.contains(&neighbor_pos) {
-          cost += 1;
+    for i in 0..partition.graph.order() {
+      if partition.gain(i) > self.rng.gen_range(p..0) as i64 {
+        partition.move_vertex(i);
+        if partition.in_v_2[i] {
+          moved_to_v_2.push(i);
+        } else {
+          moved_to_v_1.push(i);
         }
       }
     }
 
-    cost
-  }
-
-  // Returns the reduction in cost, if move(i) would be executed.
-  fn gain(&mut self, i: usize) -> i32 {
-    let cost_current = self.cost(Bisection::CurrentBisection);
-    self.move_vertex(i);
-
-    let cost_if_moved = self.cost(Bisection::CurrentBisection);
-    // Reverse previous move
-    self.move_vertex(i);
-
-    // Gain can be negative. Cost not.
-    cost_current as i32 - cost_if_moved as i32
-  }
-
-  fn move_vertex(&mut self, i: usize) {
-    let v_1 = &mut self.current_bisection.0;
-    let v_2 = &mut self.current_bisection.1;
-    debug_assert!(
-      (v_1.contains(&i) && !v_2.contains(&i)) || (!v_1.contains(&i) && v_2.contains(&i)),
-      "Vertex is on both or in none partition!"
-    );
-
-    let is_in_v_1_position = v_1.iter().position(|v_i| *v_i == i);
-    let is_in_v_2_position = v_2.iter().position(|v_i| *v_i == i);
-
-    if let Some(pos) = is_in_v_1_position {
-      let to_move = v_1.remove(pos);
-      v_2.push(to_move);
-    } else if let Some(pos) = is_in_v_2_position {
-      let to_move = v_2.remove(pos);
-      v_1.push(to_move);
+    // Restore the balance by moving back the vertices that made the larger partition too large
+    let max_weight = alpha * partition.graph.total_vertex_weight() as f32;
+    let heavier_is_v_2 = partition.weight_v_2 >= partition.weight_v_1();
+    let moved = if heavier_is_v_2 {
+      &mut moved_to_v_2
     } else {
-      panic!("Moving vertex between partitions not possible, because exists in none of them");
+      &mut moved_to_v_1
+    };
+    while partition.weight_of_part(heavier_is_v_2) as f32 > max_weight {
+      match moved.pop() {
+        Some(i) => partition.move_vertex(i),
+        None => break,
+      }
     }
   }
 }
 
-fn initial_bisection(vertices_count: usize) -> (Vec<usize>, Vec<usize>) {
-  (
-    (0..(vertices_count / 2)).collect(),
-    ((vertices_count / 2)..vertices_count).collect(),
-  )
-}
-
 #[cfg(test)]
 mod tests {
-  use crate::bisection::stochastic_evolution::{Bisection, StochasticEvolution};
-  use crate::tools::graphs::graph_from_wikipedia_scc;
+  use crate::bisection::directed_bisection::Bisection;
+  use crate::bisection::stochastic_evolution::{Parameters, State, StochasticEvolution};
+  use crate::bisection::weighted_digraph::WeightedDigraph;
+  use crate::graph::hash_table::HashTable;
+  use crate::tools::graphs::{graph_from_file, graph_from_wikipedia_scc};
+  use rand::rngs::StdRng;
+  use rand::{Rng, SeedableRng};
   use std::collections::HashSet;
 
   #[test]
   fn works_on_wikipedia_scc() {
     let graph = graph_from_wikipedia_scc();
-    let (partition_1, partition_2) = StochasticEvolution::new(&graph).bisection();
+    let (partition_1, partition_2) = StochasticEvolution::default().bisection(&graph);
 
-    let graph_vertices = HashSet::from_iter(graph.vertices().into_iter());
+    let graph_vertices = HashSet::from_iter(graph.vertices());
     assert_eq!(graph_vertices.len(), partition_1.len() + partition_2.len());
     assert!(partition_1.is_subset(&graph_vertices));
     assert!(partition_2.is_subset(&graph_vertices));
@@ -221,44 +211,83 @@ mod tests {
   }
 
   #[test]
-  fn move_vertex_works() {
-    let graph = graph_from_wikipedia_scc();
-    let mut algorithm = StochasticEvolution::new(&graph);
+  fn same_seed_gives_same_bisection() {
+    let graph = graph_from_file("h_001");
+    let bisection = |seed| StochasticEvolution::new(Parameters::with_seed(seed)).bisection(&graph);
 
-    let i_to_move = 3;
+    assert_eq!(bisection(3), bisection(3));
+  }
 
-    assert!(algorithm.current_bisection.0.contains(&i_to_move));
-    assert!(!algorithm.current_bisection.1.contains(&i_to_move));
+  #[test]
+  fn respects_balance_and_pass_limit() {
+    let graph = graph_from_file("h_001");
+    let parameters = Parameters {
+      alpha: 0.55,
+      max_passes: 3,
+      ..Parameters::default()
+    };
+    let (partition_1, partition_2) = StochasticEvolution::new(parameters).bisection(&graph);
 
-    algorithm.move_vertex(i_to_move);
+    let max_size = (0.55 * graph.order() as f32) as usize;
+    assert!(partition_1.len() <= max_size);
+    assert!(partition_2.len() <= max_size);
+  }
 
-    assert!(!algorithm.current_bisection.0.contains(&i_to_move));
-    assert!(algorithm.current_bisection.1.contains(&i_to_move));
+  #[test]
+  #[should_panic(expected = "p0 must be negative")]
+  fn rejects_non_negative_p0() {
+    StochasticEvolution::new(Parameters {
+      p0: 0,
+      ..Parameters::default()
+    });
   }
 
   #[test]
-  fn gain_works() {
-    let graph = graph_from_wikipedia_scc();
-    let mut algorithm = StochasticEvolution::new(&graph);
+  fn improves_initial_bisection() {
+    let graph = graph_from_file("h_001");
+    let (digraph, _) = WeightedDigraph::from_graph(&graph);
+    let initial_cost = State::new(&digraph, 0).partition.cost;
+    let (partition_1, partition_2) = StochasticEvolution::default().bisection(&graph);
 
-    let i_to_move = 3;
+    assert!(graph.edges_from_to(&partition_2, &partition_1).len() as i64 <= initial_cost);
+  }
 
-    let gain = algorithm.gain(i_to_move);
-    let gain_back = algorithm.gain(i_to_move);
+  #[test]
+  fn initial_state_works() {
+    let (digraph, _) = WeightedDigraph::from_graph(&graph_from_wikipedia_scc());
+    let state = State::new(&digraph, 0);
 
-    assert_eq!(gain + gain_back, 0);
-    assert_eq!(gain.abs(), gain_back.abs());
+    assert_eq!(state.partition.weight_v_2, 4);
+    assert_eq!(state.partition.cost, state.partition.recompute_cost());
+    assert_eq!(state.partition.cost, 2);
   }
 
   #[test]
-  fn cost_works() {
-    let graph = graph_from_wikipedia_scc();
-    let mut algorithm = StochasticEvolution::new(&graph);
-
-    let cost_1 = algorithm.cost(Bisection::CurrentBisection);
-    let cost_2 = algorithm.cost(Bisection::CurrentBisection);
+  fn self_loops_do_not_change_the_cost() {
+    let graph = HashTable::from_edges(&[(0, 0), (0, 1), (1, 1), (1, 2), (2, 0), (3, 3), (3, 0)]);
+    let (digraph, _) = WeightedDigraph::from_graph(&graph);
+    let mut state = State::new(&digraph, 0);
+    let mut rng = StdRng::seed_from_u64(2);
+
+    for _ in 0..100 {
+      let i = rng.gen_range(0..digraph.order());
+      let expected = state.partition.cost - state.partition.gain(i);
+      state.partition.move_vertex(i);
+      assert_eq!(state.partition.cost, expected);
+      assert_eq!(state.partition.cost, state.partition.recompute_cost());
+    }
+  }
 
-    assert_eq!(cost_1, cost_2);
-    assert_eq!(cost_1, 2);
+  #[test]
+  fn perturb_keeps_balance() {
+    let graph = HashTable::random(60, 0.1, &mut StdRng::seed_from_u64(1));
+    let (digraph, _) = WeightedDigraph::from_graph(&graph);
+    let mut state = State::new(&digraph, 0);
+
+    for p in [-1, -5, -20] {
+      state.perturb(p, 0.6);
+      assert!(state.partition.weight_v_2 <= 36 && state.partition.weight_v_1() <= 36);
+      assert_eq!(state.partition.cost, state.partition.recompute_cost());
+    }
   }
 }
@@ -0,0 +1,369 @@
+use crate::graph::hash_table::{HashTable, VertexId};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashMap;
+
+/// Digraph on vertex indices with vertex and arc weights. Partitioners work on this
+/// representation, so that coarsened graphs (see multilevel) can be refined the same way.
+#[derive(Clone, Debug)]
+pub(crate) struct WeightedDigraph {
+  pub vertex_weights: Vec<i64>,
+  pub successors: Vec<Vec<(usize, i64)>>,
+  pub predecessors: Vec<Vec<(usize, i64)>>,
+}
+
+impl WeightedDigraph {
+  /// Returns the digraph with unit weights and the vertex id of every index. Self-loops are
+  /// left out, because they never point from one part to the other.
+  pub fn from_graph(graph: &HashTable) -> (Self, Vec<VertexId>) {
+    let vertices = graph.vertices();
+    let index = vertices
+      .iter()
+      .enumerate()
+      .map(|(i, v)| (*v, i))
+      .collect::<HashMap<_, _>>();
+
+    let mut digraph = Self::with_vertex_weights(vec![1; vertices.len()]);
+    for (source, destination) in graph.all_edges() {
+      if source != destination {
+        digraph.add_arc(index[&source], index[&destination], 1);
+      }
+    }
+    (digraph, vertices)
+  }
+
+  pub fn with_vertex_weights(vertex_weights: Vec<i64>) -> Self {
+    let n = vertex_weights.len();
+    Self {
+      vertex_weights,
+      successors: vec![vec![]; n],
+      predecessors: vec![vec![]; n],
+    }
+  }
+
+  /// Adds the arc (u, v), parallel arcs are not merged
+  pub fn add_arc(&mut self, u: usize, v: usize, weight: i64) {
+    self.successors[u].push((v, weight));
+    self.predecessors[v].push((u, weight));
+  }
+
+  pub fn order(&self) -> usize {
+    self.vertex_weights.len()
+  }
+
+  pub fn total_vertex_weight(&self) -> i64 {
+    self.vertex_weights.iter().sum()
+  }
+
+  /// Weight of the arcs between u and v in both directions, for every neighbor v of u
+  pub fn connections(&self, u: usize) -> HashMap<usize, i64> {
+    let mut connections = HashMap::new();
+    for &(v, weight) in self.successors[u].iter().chain(&self.predecessors[u]) {
+      *connections.entry(v).or_default() += weight;
+    }
+    connections
+  }
+
+  /*
+  Heavy connection matching: Visits the vertices in the given order and matches every unmatched
+  vertex with the unmatched neighbor it has the heaviest connection to, unless both together
+  weigh more than max_vertex_weight. Returns the coarse vertex of every vertex and the number of
+  coarse vertices.
+   */
+  pub fn heavy_matching(&self, order: &[usize], max_vertex_weight: i64) -> (Vec<usize>, usize) {
+    let mut coarse = vec![usize::MAX; self.order()];
+    let mut coarse_order = 0;
+    for &u in order {
+      if coarse[u] != usize::MAX {
+        continue;
+      }
+      let partner = self
+        .connections(u)
+        .into_iter()
+        .filter(|(v, _)| {
+          *v != u
+            && coarse[*v] == usize::MAX
+            && self.vertex_weights[u] + self.vertex_weights[*v] <= max_vertex_weight
+        })
+        // Ties are broken by the index, because the order of a HashMap is random
+        .max_by_key(|(v, weight)| (*weight, usize::MAX - v));
+      coarse[u] = coarse_order;
+      if let Some((v, _)) = partner {
+        coarse[v] = coarse_order;
+      }
+      coarse_order += 1;
+    }
+    (coarse, coarse_order)
+  }
+
+  /// Merges the vertices with the same coarse vertex. Vertex weights and the weights of parallel
+  /// arcs are added up, arcs inside a coarse vertex are dropped.
+  pub fn contract(&self, coarse: &[usize], coarse_order: usize) -> Self {
+    let mut vertex_weights = vec![0; coarse_order];
+    for (u, weight) in self.vertex_weights.iter().enumerate() {
+      vertex_weights[coarse[u]] += weight;
+    }
+
+    let mut arcs = HashMap::<(usize, usize), i64>::new();
+    for (u, successors) in self.successors.iter().enumerate() {
+      for &(v, weight) in successors {
+        if coarse[u] != coarse[v] {
+          *arcs.entry((coarse[u], coarse[v])).or_default() += weight;
+        }
+      }
+    }
+    let mut arcs = arcs.into_iter().collect::<Vec<_>>();
+    arcs.sort_unstable();
+
+    let mut contracted = Self::with_vertex_weights(vertex_weights);
+    for ((u, v), weight) in arcs {
+      contracted.add_arc(u, v, weight);
+    }
+    contracted
+  }
+
+  /// Weight of all arcs from u to v
+  pub fn arc_weight(&self, u: usize, v: usize) -> i64 {
+    self.successors[u]
+      .iter()
+      .filter(|(w, _)| *w == v)
+      .map(|(_, weight)| weight)
+      .sum()
+  }
+}
+
+/// A coarsened graph and the coarse vertex of every vertex of the next finer graph
+pub(crate) struct Level {
+  pub graph: WeightedDigraph,
+  pub coarse: Vec<usize>,
+}
+
+/*
+Contracts matched vertex pairs until the graph has at most coarsest_order vertices. The matching
+gets the vertices in random order and returns the coarse vertex of every vertex and their number.
+Coarsening stops early, if less than 10% of the vertices could be matched.
+ */
+pub(crate) fn coarsen<R, M>(
+  graph: &WeightedDigraph,
+  coarsest_order: usize,
+  rng: &mut R,
+  mut matching: M,
+) -> Vec<Level>
+where
+  R: Rng,
+  M: FnMut(&WeightedDigraph, &[usize]) -> (Vec<usize>, usize),
+{
+  let mut levels: Vec<Level> = vec![];
+  loop {
+    let finer = levels.last().map_or(graph, |level| &level.graph);
+    if finer.order() <= coarsest_order {
+      break;
+    }
+
+    let mut order = (0..finer.order()).collect::<Vec<_>>();
+    order.shuffle(rng);
+    let (coarse, coarse_order) = matching(finer, &order);
+    if coarse_order * 10 > finer.order() * 9 {
+      break;
+    }
+
+    let coarser = finer.contract(&coarse, coarse_order);
+    levels.push(Level {
+      graph: coarser,
+      coarse,
+    });
+  }
+  levels
+}
+
+/*
+A bisection (V1, V2) of a WeightedDigraph, whose cost is the weight of all arcs from V2 to V1.
+For every vertex the weight of its arcs from V2 (in_from_v_2) and to V1 (out_to_v_1) is kept
+up to date, so gain(i) is O(1) and move_vertex(i) is O(deg(i)):
+Moving i from V1 to V2 removes in_from_v_2(i) from the cost and adds out_to_v_1(i), and vice versa.
+ */
+pub(crate) struct Partition<'g> {
+  pub graph: &'g WeightedDigraph,
+  pub in_v_2: Vec<bool>,
+  pub weight_v_2: i64,
+  in_from_v_2: Vec<i64>,
+  out_to_v_1: Vec<i64>,
+  pub cost: i64,
+}
+
+impl<'g> Partition<'g> {
+  pub fn new(graph: &'g WeightedDigraph, in_v_2: &[bool]) -> Self {
+    let n = graph.order();
+    let mut partition = Self {
+      graph,
+      in_v_2: vec![false; n],
+      weight_v_2: 0,
+      in_from_v_2: vec![0; n],
+      out_to_v_1: (0..n)
+        .map(|i| graph.successors[i].iter().map(|(_, w)| w).sum())
+        .collect(),
+      cost: 0,
+    };
+    for (i, in_v_2) in in_v_2.iter().enumerate() {
+      if *in_v_2 {
+        partition.move_vertex(i);
+      }
+    }
+    partition
+  }
+
+  pub fn weight_v_1(&self) -> i64 {
+    self.graph.total_vertex_weight() - self.weight_v_2
+  }
+
+  /// Weight of the part vertex i is in
+  pub fn weight_of_part(&self, in_v_2: bool) -> i64 {
+    if in_v_2 {
+      self.weight_v_2
+    } else {
+      self.weight_v_1()
+    }
+  }
+
+  // Returns the reduction in cost, if move_vertex(i) would be executed.
+  pub fn gain(&self, i: usize) -> i64 {
+    let gain = self.in_from_v_2[i] - self.out_to_v_1[i];
+    if self.in_v_2[i] {
+      -gain
+    } else {
+      gain
+    }
+  }
+
+  pub fn move_vertex(&mut self, i: usize) {
+    self.cost -= self.gain(i);
+
+    let to_v_2 = !self.in_v_2[i];
+    let sign = if to_v_2 { 1 } else { -1 };
+    self.in_v_2[i] = to_v_2;
+    self.weight_v_2 += sign * self.graph.vertex_weights[i];
+
+    for &(successor, weight) in &self.graph.successors[i] {
+      self.in_from_v_2[successor] += sign * weight;
+    }
+    for &(predecessor, weight) in &self.graph.predecessors[i] {
+      self.out_to_v_1[predecessor] -= sign * weight;
+    }
+  }
+
+  // Weight of all arcs from V2 to V1, computed from scratch
+  pub fn recompute_cost(&self) -> i64 {
+    (0..self.graph.order())
+      .filter(|i| self.in_v_2[*i])
+      .flat_map(|i| self.graph.successors[i].iter())
+      .filter(|(j, _)| !self.in_v_2[*j])
+      .map(|(_, weight)| weight)
+      .sum()
+  }
+}
+
+/// Assigns the vertices in the given order to V1 until it holds half of the total weight
+pub(crate) fn balanced_split(graph: &WeightedDigraph, order: &[usize]) -> Vec<bool> {
+  let half = graph.total_vertex_weight() / 2;
+  let mut in_v_2 = vec![true; graph.order()];
+  let mut weight_v_1 = 0;
+  for &i in order {
+    if weight_v_1 + graph.vertex_weights[i] > half && weight_v_1 > 0 {
+      break;
+    }
+    weight_v_1 += graph.vertex_weights[i];
+    in_v_2[i] = false;
+  }
+  in_v_2
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::bisection::weighted_digraph::{balanced_split, Partition, WeightedDigraph};
+  use crate::graph::hash_table::HashTable;
+  use rand::rngs::StdRng;
+  use rand::{Rng, SeedableRng};
+
+  #[test]
+  fn incremental_cost_matches_recomputation() {
+    let mut digraph = WeightedDigraph::with_vertex_weights(vec![1, 2, 3, 1, 1, 2]);
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..20 {
+      let (u, v) = (rng.gen_range(0..6), rng.gen_range(0..6));
+      if u != v {
+        digraph.add_arc(u, v, rng.gen_range(1..4));
+      }
+    }
+
+    let mut partition = Partition::new(&digraph, &[false, true, false, true, false, true]);
+    assert_eq!(partition.cost, partition.recompute_cost());
+    assert_eq!(partition.weight_v_2, 5);
+
+    for _ in 0..100 {
+      let i = rng.gen_range(0..6);
+      let expected = partition.cost - partition.gain(i);
+      partition.move_vertex(i);
+      assert_eq!(partition.cost, expected);
+      assert_eq!(partition.cost, partition.recompute_cost());
+    }
+  }
+
+  #[test]
+  fn contract_merges_parallel_arcs() {
+    // Path 0 → 1 → 2 → 3 and arcs 0 → 2, 3 → 0
+    let mut digraph = WeightedDigraph::with_vertex_weights(vec![1; 4]);
+    for (u, v) in [(0, 1), (1, 2), (2, 3), (0, 2), (3, 0)] {
+      digraph.add_arc(u, v, 1);
+    }
+    let contracted = digraph.contract(&[0, 0, 1, 1], 2);
+
+    assert_eq!(contracted.vertex_weights, vec![2, 2]);
+    assert_eq!(contracted.arc_weight(0, 1), 2);
+    assert_eq!(contracted.arc_weight(1, 0), 1);
+    assert_eq!(contracted.successors[0].len(), 1);
+  }
+
+  #[test]
+  fn heavy_matching_prefers_heavy_connections() {
+    // 0 ⇄ 1 is connected twice as heavy as 1 → 2
+    let mut digraph = WeightedDigraph::with_vertex_weights(vec![1; 4]);
+    for (u, v) in [(1, 2), (0, 1), (1, 0), (2, 3)] {
+      digraph.add_arc(u, v, 1);
+    }
+
+    assert_eq!(
+      digraph.heavy_matching(&[1, 2, 3, 0], 2),
+      (vec![0, 0, 1, 1], 2)
+    );
+    // Matching 0 and 1 would exceed the vertex weight limit
+    digraph.vertex_weights[0] = 2;
+    assert_eq!(
+      digraph.heavy_matching(&[1, 2, 3, 0], 2),
+      (vec![2, 0, 0, 1], 3)
+    );
+  }
+
+  #[test]
+  fn from_graph_skips_self_loops() {
+    let graph = HashTable::from_edges(&[(1, 2), (2, 2), (2, 1)]);
+    let (digraph, vertices) = WeightedDigraph::from_graph(&graph);
+
+    assert_eq!(vertices, vec![1, 2]);
+    assert_eq!(digraph.arc_weight(0, 1), 1);
+    assert_eq!(digraph.arc_weight(1, 1), 0);
+  }
+
+  #[test]
+  fn balanced_split_halves_weight() {
+    let digraph = WeightedDigraph::with_vertex_weights(vec![3, 1, 1, 1]);
+
+    assert_eq!(
+      balanced_split(&digraph, &[0, 1, 2, 3]),
+      vec![false, true, true, true]
+    );
+    assert_eq!(
+      balanced_split(&digraph, &[1, 2, 3, 0]),
+      vec![true, false, false, false]
+    );
+  }
+}
@@ -0,0 +1,67 @@
+use std::str::FromStr;
+
+/// Minimal command line parser for options of the form `--name value` and `--flag`
+pub struct Arguments {
+  arguments: Vec<String>,
+}
+
+impl Arguments {
+  pub fn new(arguments: Vec<String>) -> Self {
+    Self { arguments }
+  }
+
+  /// Removes `--name` and returns whether it was given
+  pub fn flag(&mut self, name: &str) -> bool {
+    match self.arguments.iter().position(|a| a == name) {
+      Some(idx) => {
+        self.arguments.remove(idx);
+        true
+      }
+      None => false,
+    }
+  }
+
+  /// Removes `--name value` and returns the value
+  pub fn value(&mut self, name: &str) -> Result<Option<String>, String> {
+    match self.arguments.iter().position(|a| a == name) {
+      Some(idx) if idx + 1 < self.arguments.len() => {
+        let value = self.arguments.remove(idx + 1);
+        self.arguments.remove(idx);
+        Ok(Some(value))
+      }
+      Some(_) => Err(format!("Missing value for {name}")),
+      None => Ok(None),
+    }
+  }
+
+  /// Removes `--name value` and parses the value
+  pub fn parsed<T>(&mut self, name: &str) -> Result<Option<T>, String>
+  where
+    T: FromStr,
+    T::Err: ToString,
+  {
+    self
+      .value(name)?
+      .map(|value| {
+        value
+          .parse::<T>()
+          .map_err(|e| format!("Invalid value '{value}' for {name}: {}", e.to_string()))
+      })
+      .transpose()
+  }
+
+  /// Removes and returns the first argument that is not an option
+  pub fn positional(&mut self) -> Option<String> {
+    let idx = self.arguments.iter().position(|a| !a.starts_with("--"))?;
+    Some(self.arguments.remove(idx))
+  }
+
+  /// Fails if there are arguments left that were not consumed
+  pub fn finish(self) -> Result<(), String> {
+    if self.arguments.is_empty() {
+      Ok(())
+    } else {
+      Err(format!("Unknown arguments: {}", self.arguments.join(" ")))
+    }
+  }
+}
@@ -0,0 +1,100 @@
+use crate::cli::arguments::Arguments;
+use graphalgorithms_feedback_arc_set::generator::de_bruijn::{
+  de_bruijn, de_bruijn_order, kautz, kautz_order,
+};
+use graphalgorithms_feedback_arc_set::generator::grid::toroidal_grid;
+use graphalgorithms_feedback_arc_set::generator::layered::layered_with_noise;
+use graphalgorithms_feedback_arc_set::generator::planted::PlantedFas;
+use graphalgorithms_feedback_arc_set::generator::preferential_attachment::preferential_attachment;
+use graphalgorithms_feedback_arc_set::generator::tournament::{
+  near_transitive_tournament, random_tournament,
+};
+use graphalgorithms_feedback_arc_set::graph::hash_table::HashTable;
+use graphalgorithms_feedback_arc_set::tools::dot::Dot;
+use graphalgorithms_feedback_arc_set::tools::metis::MetisWriter;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+/// Prints a generated graph in METIS format or as dot
+pub fn generate(mut args: Arguments) -> Result<(), String> {
+  let family = args
+    .positional()
+    .ok_or_else(|| "Missing graph family".to_string())?;
+  let seed = args.parsed::<u64>("--seed")?.unwrap_or(0);
+  let format = args
+    .value("--format")?
+    .unwrap_or_else(|| "metis".to_string());
+  let mut rng = StdRng::seed_from_u64(seed);
+
+  let order = args.parsed::<usize>("--n")?;
+  if order.is_some() && ["grid", "layered", "de-bruijn", "kautz"].contains(&family.as_str()) {
+    return Err(format!(
+      "--n is not used by {family}, its size follows from the other options"
+    ));
+  }
+  let n = order.unwrap_or(100);
+  let graph = match family.as_str() {
+    "random" => HashTable::random(n, density(&mut args, 0.05)?, &mut rng),
+    "tournament" => match args.parsed::<f64>("--noise")? {
+      Some(noise) => near_transitive_tournament(n, check(noise)?, &mut rng),
+      None => random_tournament(n, &mut rng),
+    },
+    "scale-free" => {
+      let degree = args.parsed::<usize>("--degree")?.unwrap_or(2).max(1);
+      preferential_attachment(n, degree, &mut rng)
+    }
+    "grid" => {
+      let rows = args.parsed::<usize>("--rows")?.unwrap_or(10);
+      let columns = args.parsed::<usize>("--columns")?.unwrap_or(rows);
+      toroidal_grid(rows, columns)
+    }
+    "layered" => {
+      let layers = args.parsed::<usize>("--layers")?.unwrap_or(10);
+      let width = args.parsed::<usize>("--width")?.unwrap_or(10);
+      let density = density(&mut args, 0.3)?;
+      let noise = check(args.parsed::<f64>("--noise")?.unwrap_or(0.05))?;
+      layered_with_noise(layers, width, density, noise, &mut rng)
+    }
+    "de-bruijn" | "kautz" => {
+      let degree = args.parsed::<usize>("--degree")?.unwrap_or(2).max(1);
+      let length = args.parsed::<usize>("--length")?.unwrap_or(5).max(1);
+      let too_large =
+        || format!("{family} graph with degree {degree} and length {length} has too many vertices");
+      if family == "kautz" {
+        kautz_order(degree, length).ok_or_else(too_large)?;
+        kautz(degree, length)
+      } else {
+        de_bruijn_order(degree, length).ok_or_else(too_large)?;
+        de_bruijn(degree, length)
+      }
+    }
+    "planted" => {
+      let planted = args.parsed::<usize>("--planted")?.unwrap_or(n / 10);
+      PlantedFas::new(n, density(&mut args, 0.05)?, planted)
+        .generate(&mut rng)
+        .graph
+    }
+    _ => return Err(format!("Unknown graph family '{family}'")),
+  };
+  args.finish()?;
+
+  match format.as_str() {
+    "metis" => print!("{}", MetisWriter::new(&graph)),
+    "dot" => print!("{}", Dot::new(&graph)),
+    _ => return Err(format!("Unknown format '{format}'")),
+  }
+
+  Ok(())
+}
+
+fn density(args: &mut Arguments, default: f64) -> Result<f64, String> {
+  check(args.parsed::<f64>("--density")?.unwrap_or(default))
+}
+
+fn check(probability: f64) -> Result<f64, String> {
+  if (0.0..=1.0).contains(&probability) {
+    Ok(probability)
+  } else {
+    Err(format!("{probability} is not a probability"))
+  }
+}
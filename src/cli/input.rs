@@ -0,0 +1,15 @@
+use graphalgorithms_feedback_arc_set::graph::hash_table::HashTable;
+use graphalgorithms_feedback_arc_set::tools::graphs::graph_from_reader;
+use std::fs::File;
+use std::io::{self, BufReader};
+
+/// Reads a graph in METIS format from the given file, or from stdin if no file is given
+pub fn read_graph(path: Option<&str>) -> Result<HashTable, String> {
+  match path {
+    None | Some("-") => Ok(graph_from_reader(io::stdin().lock())),
+    Some(path) => {
+      let file = File::open(path).map_err(|e| format!("Cannot open {path}: {e}"))?;
+      Ok(graph_from_reader(BufReader::new(file)))
+    }
+  }
+}
@@ -0,0 +1,7 @@
+pub mod arguments;
+pub mod generate;
+pub mod input;
+pub mod portfolio;
+pub mod quality;
+pub mod solve;
+pub mod stats;
@@ -0,0 +1,47 @@
+use crate::cli::arguments::Arguments;
+use crate::cli::input::read_graph;
+use graphalgorithms_feedback_arc_set::fas::algorithm::Algorithm;
+use graphalgorithms_feedback_arc_set::solver::pace::write_arcs;
+use graphalgorithms_feedback_arc_set::solver::portfolio::Portfolio;
+use std::io;
+use std::time::Duration;
+
+const DEFAULT_ALGORITHMS: [Algorithm; 3] = [
+  Algorithm::Greedy,
+  Algorithm::PageRank,
+  Algorithm::SimulatedAnnealing,
+];
+
+/// Runs several algorithms on every SCC, prints the best FAS and which algorithm won where to stderr
+pub fn portfolio(mut args: Arguments) -> Result<(), String> {
+  let algorithms = match args.value("--algorithms")? {
+    Some(names) => names
+      .split(',')
+      .map(|name| name.parse::<Algorithm>())
+      .collect::<Result<Vec<_>, _>>()?,
+    None => DEFAULT_ALGORITHMS.to_vec(),
+  };
+  let time_limit = args
+    .parsed::<f64>("--time-limit")?
+    .map(Duration::from_secs_f64);
+  let threads = args.parsed::<usize>("--threads")?;
+  let seed = args.parsed::<u64>("--seed")?.unwrap_or(0);
+  let input = args.positional();
+  args.finish()?;
+  if algorithms.is_empty() || threads == Some(0) {
+    return Err("At least one algorithm and one thread are required".to_string());
+  }
+
+  let graph = read_graph(input.as_deref())?;
+  let mut portfolio = Portfolio::new(&graph, algorithms).with_seed(seed);
+  if let Some(time_limit) = time_limit {
+    portfolio = portfolio.with_time_budget(time_limit);
+  }
+  if let Some(threads) = threads {
+    portfolio = portfolio.with_threads(threads);
+  }
+
+  let (fas, report) = portfolio.solve();
+  eprint!("{report}");
+  write_arcs(&mut io::stdout().lock(), &fas).map_err(|e| e.to_string())
+}
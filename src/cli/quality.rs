@@ -0,0 +1,48 @@
+use crate::cli::arguments::Arguments;
+use graphalgorithms_feedback_arc_set::fas::algorithm::Algorithm;
+use graphalgorithms_feedback_arc_set::tools::manifest::Manifest;
+use graphalgorithms_feedback_arc_set::tools::quality::{
+  bundled_instances, instances_from_directory, QualityHarness,
+};
+use std::fs;
+
+/// Compares the FAS sizes of the algorithms and writes <output>.csv and <output>.md
+pub fn quality(mut args: Arguments) -> Result<(), String> {
+  let seeds = args.parsed::<u64>("--seeds")?.unwrap_or(5);
+  let algorithms = match args.value("--algorithms")? {
+    Some(names) => names
+      .split(',')
+      .map(|name| name.parse::<Algorithm>())
+      .collect::<Result<Vec<_>, _>>()?,
+    None => Algorithm::ALL.to_vec(),
+  };
+  let output = args
+    .value("--output")?
+    .unwrap_or_else(|| "target/quality".to_string());
+  let directory = args.value("--dir")?;
+  let manifest = args.value("--manifest")?;
+  args.finish()?;
+
+  let instances = match &directory {
+    Some(directory) => instances_from_directory(directory),
+    None => bundled_instances(),
+  }
+  .map_err(|e| format!("Cannot load instances: {e}"))?;
+  let manifest = match (manifest, directory) {
+    (Some(path), _) => Manifest::load(&path).map_err(|e| format!("Cannot load {path}: {e}"))?,
+    (None, None) => Manifest::bundled().map_err(|e| format!("Cannot load manifest: {e}"))?,
+    (None, Some(_)) => Manifest::default(),
+  };
+
+  let report = QualityHarness::new(algorithms, seeds.max(1))
+    .with_manifest(manifest)
+    .run(&instances);
+  let markdown = report.to_markdown();
+  for (extension, content) in [("csv", report.to_csv()), ("md", markdown.clone())] {
+    let path = format!("{output}.{extension}");
+    fs::write(&path, content).map_err(|e| format!("Cannot write {path}: {e}"))?;
+  }
+  print!("{markdown}");
+
+  Ok(())
+}
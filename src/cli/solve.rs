@@ -0,0 +1,84 @@
+use crate::cli::arguments::Arguments;
+use crate::cli::input::read_graph;
+use graphalgorithms_feedback_arc_set::fas::algorithm::Algorithm;
+use graphalgorithms_feedback_arc_set::fas::lower_bound::cycle_packing_bound;
+use graphalgorithms_feedback_arc_set::fas::solution::FasSolution;
+use graphalgorithms_feedback_arc_set::solver::anytime::{trivial_feedback_arc_set, AnytimeSolver};
+use graphalgorithms_feedback_arc_set::solver::incumbent::Incumbent;
+use graphalgorithms_feedback_arc_set::solver::pace::{
+  feedback_vertex_set, write_arcs, write_vertices,
+};
+use signal_hook::consts::{SIGINT, SIGTERM};
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Solves the instance as an anytime process and prints the best solution as soon as the
+/// algorithm is done, SIGTERM (or SIGINT) is received or the time limit is reached.
+pub fn solve(mut args: Arguments) -> Result<(), String> {
+  let start = Instant::now();
+  let algorithm = args
+    .parsed::<Algorithm>("--algorithm")?
+    .unwrap_or(Algorithm::Greedy);
+  let time_limit = args
+    .parsed::<f64>("--time-limit")?
+    .map(Duration::from_secs_f64);
+  let seed = args.parsed::<u64>("--seed")?.unwrap_or(0);
+  let print_vertices = args.flag("--dfvs");
+  let print_summary = args.flag("--summary");
+  let input = args.positional();
+  args.finish()?;
+
+  let stop = Arc::new(AtomicBool::new(false));
+  for signal in [SIGTERM, SIGINT] {
+    signal_hook::flag::register(signal, stop.clone()).map_err(|e| e.to_string())?;
+  }
+
+  let graph = Arc::new(read_graph(input.as_deref())?);
+  let incumbent = Arc::new(Incumbent::new());
+  incumbent.offer(trivial_feedback_arc_set(&graph));
+
+  let worker = {
+    let (graph, incumbent, stop) = (graph.clone(), incumbent.clone(), stop.clone());
+    thread::spawn(move || AnytimeSolver::new(&graph, algorithm, seed, incumbent, stop).run())
+  };
+
+  let deadline = time_limit.map(|limit| start + limit);
+  while !worker.is_finished()
+    && !stop.load(Ordering::Relaxed)
+    && deadline.is_none_or(|deadline| Instant::now() < deadline)
+  {
+    thread::sleep(POLL_INTERVAL);
+  }
+  stop.store(true, Ordering::Relaxed);
+
+  // The worker is not joined: returning from main ends the process even if an algorithm run is still going on.
+  // The answer is written first, the summary may take a while and must not delay or suppress it.
+  let fas = incumbent.best().unwrap();
+  let mut stdout = io::stdout().lock();
+  let written = if print_vertices {
+    write_vertices(&mut stdout, &feedback_vertex_set(&fas))
+  } else {
+    write_arcs(&mut stdout, &fas)
+  };
+  written
+    .and_then(|_| stdout.flush())
+    .map_err(|e| e.to_string())?;
+  drop(stdout);
+  let wall_time = start.elapsed();
+
+  if print_summary {
+    let solution = FasSolution::new(&graph, fas)?
+      // Randomized algorithms are restarted with seed, seed + 1, ..., so these are the parameters of the first run
+      .with_algorithm(algorithm.name(), &algorithm.parameters(seed))
+      .with_wall_time(wall_time)
+      .with_lower_bound(cycle_packing_bound(&graph).bound);
+    solution.verify(&graph)?;
+    eprintln!("{solution}");
+  }
+  Ok(())
+}
@@ -0,0 +1,19 @@
+use crate::cli::arguments::Arguments;
+use crate::cli::input::read_graph;
+use graphalgorithms_feedback_arc_set::graph::statistics::GraphStatistics;
+
+/// Prints the statistics of a graph, human-readable or as JSON
+pub fn stats(mut args: Arguments) -> Result<(), String> {
+  let json = args.flag("--json");
+  let input = args.positional();
+  args.finish()?;
+
+  let statistics = GraphStatistics::new(&read_graph(input.as_deref())?);
+  if json {
+    println!("{}", statistics.to_json());
+  } else {
+    print!("{statistics}");
+  }
+
+  Ok(())
+}
@@ -0,0 +1,314 @@
+use crate::bisection::fiduccia_mattheyses::{self, FiducciaMattheyses};
+use crate::bisection::kernighan_lin::{self, KernighanLin};
+use crate::bisection::multilevel::{self, Multilevel};
+use crate::bisection::stochastic_evolution;
+use crate::fas::bubble_sort::BubbleSort;
+use crate::fas::chanas_kobylanski::ChanasKobylanski;
+use crate::fas::divide_and_conquer_by_bisection_heuristic::DivideAndConquerByBisectionHeuristic;
+use crate::fas::divide_and_conquer_by_order_heuristic::DivideAndConquerByOrderHeuristic;
+use crate::fas::feedback_arc_set::FeedbackArcSet;
+use crate::fas::greedy::GreedyHeuristic;
+use crate::fas::kwik_sort::{self, KwikSort};
+use crate::fas::multilevel_heuristic::{self, MultilevelHeuristic};
+use crate::fas::page_rank::{self, PageRankHeuristic};
+use crate::fas::simple_heuristic::SimpleHeuristic;
+use crate::fas::simulated_annealing::{self, SimulatedAnnealing};
+use crate::fas::solution::FasSolution;
+use crate::fas::sort_fas::SortFas;
+use crate::fas::spectral::{self, SpectralHeuristic};
+use crate::fas::tabu_search::{self, TabuSearch};
+use crate::graph::hash_table::{Edge, HashTable};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::Instant;
+
+/// All registered feedback arc set algorithms, so they can be selected by name (e.g. on the command line).
+/// DivideAndConquerByBisectionHeuristic is registered once per bisection, so the partitioners can be compared.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Algorithm {
+  Simple,
+  Greedy,
+  DivideAndConquerByOrder,
+  DivideAndConquerByBisection,
+  DivideAndConquerByBisectionFm,
+  DivideAndConquerByBisectionKl,
+  DivideAndConquerByBisectionMultilevel,
+  Multilevel,
+  SortFas,
+  ChanasKobylanski,
+  BubbleSort,
+  KwikSort,
+  PageRank,
+  Spectral,
+  SimulatedAnnealing,
+  TabuSearch,
+}
+
+impl Algorithm {
+  pub const ALL: [Algorithm; 16] = [
+    Algorithm::Simple,
+    Algorithm::Greedy,
+    Algorithm::DivideAndConquerByOrder,
+    Algorithm::DivideAndConquerByBisection,
+    Algorithm::DivideAndConquerByBisectionFm,
+    Algorithm::DivideAndConquerByBisectionKl,
+    Algorithm::DivideAndConquerByBisectionMultilevel,
+    Algorithm::Multilevel,
+    Algorithm::SortFas,
+    Algorithm::ChanasKobylanski,
+    Algorithm::BubbleSort,
+    Algorithm::KwikSort,
+    Algorithm::PageRank,
+    Algorithm::Spectral,
+    Algorithm::SimulatedAnnealing,
+    Algorithm::TabuSearch,
+  ];
+
+  /// The name used to select the algorithm
+  pub fn name(&self) -> &'static str {
+    match self {
+      Algorithm::Simple => "simple",
+      Algorithm::Greedy => "greedy",
+      Algorithm::DivideAndConquerByOrder => "divide_and_conquer_by_order",
+      Algorithm::DivideAndConquerByBisection => "divide_and_conquer_by_bisection",
+      Algorithm::DivideAndConquerByBisectionFm => "divide_and_conquer_by_bisection_fm",
+      Algorithm::DivideAndConquerByBisectionKl => "divide_and_conquer_by_bisection_kl",
+      Algorithm::DivideAndConquerByBisectionMultilevel => {
+        "divide_and_conquer_by_bisection_multilevel"
+      }
+      Algorithm::Multilevel => "multilevel",
+      Algorithm::SortFas => "sort_fas",
+      Algorithm::ChanasKobylanski => "chanas_kobylanski",
+      Algorithm::BubbleSort => "bubble_sort",
+      Algorithm::KwikSort => "kwik_sort",
+      Algorithm::PageRank => "page_rank",
+      Algorithm::Spectral => "spectral",
+      Algorithm::SimulatedAnnealing => "simulated_annealing",
+      Algorithm::TabuSearch => "tabu_search",
+    }
+  }
+
+  /// Whether the result depends on the seed
+  pub fn is_randomized(&self) -> bool {
+    !matches!(
+      self,
+      Algorithm::Greedy
+        | Algorithm::DivideAndConquerByOrder
+        | Algorithm::SortFas
+        | Algorithm::ChanasKobylanski
+        | Algorithm::BubbleSort
+        | Algorithm::PageRank
+    )
+  }
+
+  /// Computes a feedback arc set of the graph with this algorithm.
+  /// The seed is ignored by deterministic algorithms.
+  pub fn feedback_arc_set(&self, graph: &HashTable, seed: u64) -> HashSet<Edge> {
+    self.feedback_arc_set_with_deadline(graph, seed, None)
+  }
+
+  /// Like feedback_arc_set, but time-aware algorithms (simulated annealing and tabu search) stop
+  /// at the deadline. All other algorithms run to completion.
+  pub fn feedback_arc_set_with_deadline(
+    &self,
+    graph: &HashTable,
+    seed: u64,
+    deadline: Option<Instant>,
+  ) -> HashSet<Edge> {
+    let time_limit = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+    match self {
+      Algorithm::Simple => SimpleHeuristic::with_seed(graph, seed).feedback_arc_set(),
+      Algorithm::Greedy => GreedyHeuristic::new(graph).feedback_arc_set(),
+      Algorithm::DivideAndConquerByOrder => {
+        DivideAndConquerByOrderHeuristic::new(graph).feedback_arc_set()
+      }
+      Algorithm::DivideAndConquerByBisection => {
+        DivideAndConquerByBisectionHeuristic::with_seed(graph, seed).feedback_arc_set()
+      }
+      Algorithm::DivideAndConquerByBisectionFm => {
+        let bisection = FiducciaMattheyses::new(fiduccia_mattheyses::Parameters::with_seed(seed));
+        DivideAndConquerByBisectionHeuristic::with_bisection(graph, bisection).feedback_arc_set()
+      }
+      Algorithm::DivideAndConquerByBisectionKl => {
+        let bisection = KernighanLin::new(kernighan_lin::Parameters::with_seed(seed));
+        DivideAndConquerByBisectionHeuristic::with_bisection(graph, bisection).feedback_arc_set()
+      }
+      Algorithm::DivideAndConquerByBisectionMultilevel => {
+        let bisection = Multilevel::new(multilevel::Parameters::with_seed(seed));
+        DivideAndConquerByBisectionHeuristic::with_bisection(graph, bisection).feedback_arc_set()
+      }
+      Algorithm::Multilevel => MultilevelHeuristic::with_seed(graph, seed).feedback_arc_set(),
+      Algorithm::SortFas => SortFas::new(graph).feedback_arc_set(),
+      Algorithm::ChanasKobylanski => ChanasKobylanski::new(graph).feedback_arc_set(),
+      Algorithm::BubbleSort => BubbleSort::new(graph).feedback_arc_set(),
+      Algorithm::KwikSort => KwikSort::with_seed(graph, seed).feedback_arc_set(),
+      Algorithm::PageRank => PageRankHeuristic::new(graph).feedback_arc_set(),
+      Algorithm::Spectral => SpectralHeuristic::with_seed(graph, seed).feedback_arc_set(),
+      Algorithm::SimulatedAnnealing => {
+        let parameters = simulated_annealing::Parameters {
+          time_limit,
+          ..simulated_annealing::Parameters::with_seed(seed)
+        };
+        SimulatedAnnealing::with_parameters(graph, parameters).feedback_arc_set()
+      }
+      Algorithm::TabuSearch => {
+        let parameters = tabu_search::Parameters {
+          time_limit,
+          ..tabu_search::Parameters::with_seed(seed)
+        };
+        TabuSearch::with_parameters(graph, parameters).feedback_arc_set()
+      }
+    }
+  }
+
+  /// The parameters feedback_arc_set uses with this seed, empty if there are none
+  pub fn parameters(&self, seed: u64) -> String {
+    match self {
+      Algorithm::Simple
+      | Algorithm::Greedy
+      | Algorithm::DivideAndConquerByOrder
+      | Algorithm::SortFas
+      | Algorithm::ChanasKobylanski
+      | Algorithm::BubbleSort => String::new(),
+      Algorithm::PageRank => format!("{:?}", page_rank::Parameters::default()),
+      Algorithm::DivideAndConquerByBisection => {
+        format!("{:?}", stochastic_evolution::Parameters::with_seed(seed))
+      }
+      Algorithm::DivideAndConquerByBisectionFm => {
+        format!("{:?}", fiduccia_mattheyses::Parameters::with_seed(seed))
+      }
+      Algorithm::DivideAndConquerByBisectionKl => {
+        format!("{:?}", kernighan_lin::Parameters::with_seed(seed))
+      }
+      Algorithm::DivideAndConquerByBisectionMultilevel => {
+        format!("{:?}", multilevel::Parameters::with_seed(seed))
+      }
+      Algorithm::Multilevel => format!("{:?}", multilevel_heuristic::Parameters::with_seed(seed)),
+      Algorithm::KwikSort => format!("{:?}", kwik_sort::Parameters::with_seed(seed)),
+      Algorithm::Spectral => format!("{:?}", spectral::Parameters::with_seed(seed)),
+      Algorithm::SimulatedAnnealing => {
+        format!("{:?}", simulated_annealing::Parameters::with_seed(seed))
+      }
+      Algorithm::TabuSearch => format!("{:?}", tabu_search::Parameters::with_seed(seed)),
+    }
+  }
+
+  /// Like feedback_arc_set, but returns the FAS with its certificate, parameters and wall time
+  pub fn solve(&self, graph: &HashTable, seed: u64) -> FasSolution {
+    let start = Instant::now();
+    let fas = self.feedback_arc_set(graph, seed);
+    let wall_time = start.elapsed();
+
+    let solution = FasSolution::new(graph, fas)
+      .unwrap_or_else(|e| panic!("{self} returned an invalid solution: {e}"))
+      .with_algorithm(self.name(), &self.parameters(seed))
+      .with_wall_time(wall_time);
+    if self.is_randomized() {
+      solution.with_seed(seed)
+    } else {
+      solution
+    }
+  }
+}
+
+impl Display for Algorithm {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
+
+impl FromStr for Algorithm {
+  type Err = String;
+
+  fn from_str(name: &str) -> Result<Self, Self::Err> {
+    Algorithm::ALL
+      .into_iter()
+      .find(|algorithm| algorithm.name() == name)
+      .ok_or_else(|| {
+        let names = Algorithm::ALL.map(|algorithm| algorithm.name());
+        format!(
+          "Unknown algorithm '{name}', expected one of: {}",
+          names.join(", ")
+        )
+      })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::fas::algorithm::Algorithm;
+  use crate::generator::de_bruijn::de_bruijn;
+  use crate::tools::graphs::graph_from_wikipedia_scc;
+  use std::time::Instant;
+
+  #[test]
+  fn names_round_trip() {
+    for algorithm in Algorithm::ALL {
+      assert_eq!(algorithm.name().parse::<Algorithm>(), Ok(algorithm));
+    }
+    assert!("unknown".parse::<Algorithm>().is_err());
+  }
+
+  #[test]
+  fn all_algorithms_break_all_cycles() {
+    // The de Bruijn graph B(2, 4) has the self-loops 0 → 0 and 15 → 15
+    for graph in [graph_from_wikipedia_scc(), de_bruijn(2, 4)] {
+      for algorithm in Algorithm::ALL {
+        let mut acyclic_graph = graph.clone();
+        algorithm
+          .feedback_arc_set(&graph, 0)
+          .into_iter()
+          .for_each(|e| acyclic_graph.remove_edge(e));
+
+        assert!(!acyclic_graph.is_cyclic(), "{algorithm} left a cycle");
+      }
+    }
+  }
+
+  #[test]
+  fn solve_returns_verified_solution() {
+    let graph = graph_from_wikipedia_scc();
+
+    for algorithm in Algorithm::ALL {
+      let solution = algorithm.solve(&graph, 3);
+      assert_eq!(solution.verify(&graph), Ok(()), "{algorithm}");
+      assert_eq!(solution.algorithm, algorithm.name());
+      assert_eq!(solution.seed.is_some(), algorithm.is_randomized());
+    }
+    assert!(Algorithm::Multilevel
+      .solve(&graph, 3)
+      .parameters
+      .contains("seed: 3"));
+  }
+
+  #[test]
+  fn time_aware_algorithms_stop_at_deadline() {
+    let graph = crate::tools::graphs::graph_from_file("h_001");
+    let greedy = Algorithm::Greedy.feedback_arc_set(&graph, 0);
+
+    for algorithm in [Algorithm::SimulatedAnnealing, Algorithm::TabuSearch] {
+      let start = Instant::now();
+      let fas = algorithm.feedback_arc_set_with_deadline(&graph, 0, Some(start));
+      assert!(
+        start.elapsed() < std::time::Duration::from_secs(1),
+        "{algorithm}"
+      );
+      // Both start from the ordering of GreedyHeuristic
+      assert!(fas.len() <= greedy.len(), "{algorithm}");
+    }
+  }
+
+  #[test]
+  fn all_algorithms_are_reproducible() {
+    let graph = crate::tools::graphs::graph_from_file("h_001");
+
+    for algorithm in Algorithm::ALL {
+      assert_eq!(
+        algorithm.feedback_arc_set(&graph, 42),
+        algorithm.feedback_arc_set(&graph, 42),
+        "{algorithm}"
+      );
+    }
+  }
+}
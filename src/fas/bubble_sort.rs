@@ -0,0 +1,98 @@
+/*
+BubbleSort by Brandenburg and Hanauer (Sorting heuristics for the feedback arc set problem, 2011),
+which fixes an ordering by adjacent swaps:
+
+repeat
+    for i from 1 to |V| - 1
+        if w(s[i + 1] → s[i]) > w(s[i] → s[i + 1]) then swap s[i] and s[i + 1]
+until no swap was made
+
+Every swap removes more leftward arcs than it adds, so the loop terminates after at most |E| swaps.
+ */
+
+use crate::bisection::weighted_digraph::WeightedDigraph;
+use crate::fas::feedback_arc_set::FeedbackArcSet;
+use crate::fas::sort_fas::indices;
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use crate::ordering::topological_sort::leftward_edges;
+use crate::ordering::vertex_ordering::VertexOrdering;
+use std::collections::HashSet;
+
+pub struct BubbleSort<'a> {
+  pub graph: &'a HashTable,
+  /// The ordering to fix, e.g. the one of another heuristic
+  pub initial_ordering: Vec<VertexId>,
+}
+
+impl<'a> BubbleSort<'a> {
+  /// Starts with the vertices by increasing id
+  pub fn new(graph: &'a HashTable) -> Self {
+    Self::with_ordering(graph, graph.vertices())
+  }
+
+  pub fn with_ordering(graph: &'a HashTable, initial_ordering: Vec<VertexId>) -> Self {
+    Self {
+      graph,
+      initial_ordering,
+    }
+  }
+}
+
+impl<'a> VertexOrdering for BubbleSort<'a> {
+  fn vertex_ordering(&self) -> Vec<VertexId> {
+    let (digraph, vertices) = WeightedDigraph::from_graph(self.graph);
+    let mut ordering = indices(&vertices, &self.initial_ordering);
+
+    let mut swapped = true;
+    while swapped {
+      swapped = false;
+      for i in 1..ordering.len() {
+        let (u, v) = (ordering[i - 1], ordering[i]);
+        if digraph.arc_weight(v, u) > digraph.arc_weight(u, v) {
+          ordering.swap(i - 1, i);
+          swapped = true;
+        }
+      }
+    }
+    ordering.into_iter().map(|i| vertices[i]).collect()
+  }
+}
+
+impl<'a> FeedbackArcSet for BubbleSort<'a> {
+  fn feedback_arc_set(&self) -> HashSet<Edge> {
+    leftward_edges(self.graph, self.vertex_ordering())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::fas::feedback_arc_set::tests::fas_tests;
+  use crate::fas::greedy::GreedyHeuristic;
+  use crate::tools::graphs::graph_from_file;
+
+  fas_tests!(BubbleSort, [h_001, h_025]);
+
+  #[test]
+  fn swaps_only_adjacent_vertices() {
+    // 0 and 3 are not adjacent, so 2 → 1 can not be fixed
+    let graph = HashTable::from_edges(&[(3, 2), (2, 1), (1, 0)]);
+    let bubble_sort = BubbleSort::new(&graph);
+
+    assert_eq!(bubble_sort.vertex_ordering(), vec![1, 0, 3, 2]);
+    assert_eq!(bubble_sort.feedback_arc_set(), HashSet::from([(2, 1)]));
+  }
+
+  #[test]
+  fn never_worsens_initial_ordering() {
+    let graph = graph_from_file("h_001");
+    let ordering = GreedyHeuristic::new(&graph).vertex_ordering();
+    let fixed = BubbleSort::with_ordering(&graph, ordering.clone());
+
+    assert!(fixed.feedback_arc_set().len() <= leftward_edges(&graph, ordering).len());
+    assert!(
+      BubbleSort::new(&graph).feedback_arc_set().len()
+        < leftward_edges(&graph, graph.vertices()).len()
+    );
+  }
+}
@@ -0,0 +1,199 @@
+/*
+Multiple insertion with reversal by Chanas and Kobylański (A new heuristic algorithm solving the
+linear ordering problem, 1996):
+
+s := initial ordering
+repeat
+    repeat
+        for every vertex v of s
+            move v to the position with the fewest leftward arcs (see SortFas)
+    until no vertex was moved
+    s' := ∅
+    for every vertex v of s in reversed order
+        insert v into s' at the position with the fewest leftward arcs, the front wins ties
+    s := s'
+until the leftward arcs of s did not decrease in this round
+repeat the multiple insertion on s until no vertex was moved
+return s
+
+The rebuild never has more leftward arcs than s: when v is inserted, s' holds the vertices after v
+in s with at most their leftward arcs in s, and in front of s' v adds exactly its leftward arcs in s.
+The rebuilt ordering is usually no local optimum any more, so the next round can escape it, and
+the final insertions make the result one.
+ */
+
+use crate::bisection::weighted_digraph::WeightedDigraph;
+use crate::fas::feedback_arc_set::FeedbackArcSet;
+use crate::fas::sort_fas::{indices, Insertion};
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use crate::ordering::topological_sort::leftward_edges;
+use crate::ordering::vertex_ordering::VertexOrdering;
+use std::collections::HashSet;
+
+pub struct ChanasKobylanski<'a> {
+  pub graph: &'a HashTable,
+  pub initial_ordering: Vec<VertexId>,
+  /// Upper bound for the number of rounds of insertions and reversal
+  pub max_rounds: usize,
+}
+
+impl<'a> ChanasKobylanski<'a> {
+  /// Starts with the vertices by increasing id
+  pub fn new(graph: &'a HashTable) -> Self {
+    Self::with_ordering(graph, graph.vertices())
+  }
+
+  pub fn with_ordering(graph: &'a HashTable, initial_ordering: Vec<VertexId>) -> Self {
+    Self {
+      graph,
+      initial_ordering,
+      max_rounds: 100,
+    }
+  }
+}
+
+impl<'a> VertexOrdering for ChanasKobylanski<'a> {
+  fn vertex_ordering(&self) -> Vec<VertexId> {
+    let (digraph, vertices) = WeightedDigraph::from_graph(self.graph);
+    let mut insertion = Insertion::new(&digraph);
+    let mut ordering = indices(&vertices, &self.initial_ordering);
+
+    let mut weight = leftward_weight(&digraph, &ordering);
+    for _ in 0..self.max_rounds {
+      while multiple_insertion(&mut insertion, &mut ordering) {}
+      ordering = reversed_insertion(&mut insertion, &ordering);
+
+      let previous = weight;
+      weight = leftward_weight(&digraph, &ordering);
+      if weight >= previous {
+        break;
+      }
+    }
+    while multiple_insertion(&mut insertion, &mut ordering) {}
+
+    ordering.into_iter().map(|i| vertices[i]).collect()
+  }
+}
+
+impl<'a> FeedbackArcSet for ChanasKobylanski<'a> {
+  fn feedback_arc_set(&self) -> HashSet<Edge> {
+    leftward_edges(self.graph, self.vertex_ordering())
+  }
+}
+
+// Moves every vertex once to its best position, returns whether a vertex was moved
+fn multiple_insertion(insertion: &mut Insertion, ordering: &mut Vec<usize>) -> bool {
+  let mut moved = false;
+  for v in ordering.clone() {
+    let current = ordering.iter().position(|u| *u == v).unwrap();
+    ordering.remove(current);
+    let (position, difference) = insertion.best_position(ordering, v, current);
+    ordering.insert(position, v);
+    moved |= difference < 0;
+  }
+  moved
+}
+
+// Rebuilds the ordering by inserting its vertices from last to first
+fn reversed_insertion(insertion: &mut Insertion, ordering: &[usize]) -> Vec<usize> {
+  let mut rebuilt = Vec::with_capacity(ordering.len());
+  for v in ordering.iter().rev() {
+    let (position, _) = insertion.best_position(&rebuilt, *v, 0);
+    rebuilt.insert(position, *v);
+  }
+  rebuilt
+}
+
+fn leftward_weight(graph: &WeightedDigraph, ordering: &[usize]) -> i64 {
+  let mut position = vec![0; ordering.len()];
+  for (p, v) in ordering.iter().enumerate() {
+    position[*v] = p;
+  }
+  (0..graph.order())
+    .flat_map(|u| graph.successors[u].iter().map(move |(v, w)| (u, *v, *w)))
+    .filter(|(u, v, _)| position[*v] < position[*u])
+    .map(|(_, _, w)| w)
+    .sum()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::fas::feedback_arc_set::tests::fas_tests;
+  use crate::fas::greedy::GreedyHeuristic;
+  use crate::fas::sort_fas::SortFas;
+  use crate::tools::graphs::graph_from_file;
+  use rand::rngs::StdRng;
+  use rand::seq::SliceRandom;
+  use rand::SeedableRng;
+
+  fas_tests!(ChanasKobylanski, [h_001, h_025]);
+
+  #[test]
+  fn beats_sort_fas_from_same_ordering() {
+    let graph = graph_from_file("h_025");
+    let ordering = GreedyHeuristic::new(&graph).vertex_ordering();
+
+    assert!(
+      ChanasKobylanski::with_ordering(&graph, ordering.clone())
+        .feedback_arc_set()
+        .len()
+        < SortFas::with_ordering(&graph, ordering)
+          .feedback_arc_set()
+          .len()
+    );
+  }
+
+  #[test]
+  fn reversed_insertion_never_worsens() {
+    let graph = HashTable::random(80, 0.08, &mut StdRng::seed_from_u64(0));
+    let (digraph, _) = WeightedDigraph::from_graph(&graph);
+    let mut insertion = Insertion::new(&digraph);
+    let mut ordering = (0..digraph.order()).collect::<Vec<_>>();
+    let mut rng = StdRng::seed_from_u64(1);
+
+    for _ in 0..20 {
+      ordering.shuffle(&mut rng);
+      let rebuilt = reversed_insertion(&mut insertion, &ordering);
+      assert!(leftward_weight(&digraph, &rebuilt) <= leftward_weight(&digraph, &ordering));
+    }
+  }
+
+  #[test]
+  fn reversed_insertion_moves_vertices_in_front() {
+    // 2 → 0 and 2 → 1 point leftwards in 0 1 2, inserting 2, 1, 0 puts both behind 2
+    let graph = HashTable::from_edges(&[(2, 0), (2, 1), (0, 1)]);
+    let (digraph, _) = WeightedDigraph::from_graph(&graph);
+    let mut insertion = Insertion::new(&digraph);
+
+    assert_eq!(
+      reversed_insertion(&mut insertion, &[0, 1, 2]),
+      vec![2, 0, 1]
+    );
+  }
+
+  #[test]
+  fn returns_local_optimum_for_insertions() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let graphs = (0..10)
+      .map(|_| HashTable::random(60, 0.1, &mut rng))
+      .chain([graph_from_file("h_001")]);
+
+    for graph in graphs {
+      for max_rounds in [1, 100] {
+        let ordering = ChanasKobylanski {
+          max_rounds,
+          ..ChanasKobylanski::new(&graph)
+        }
+        .vertex_ordering();
+        let (digraph, vertices) = WeightedDigraph::from_graph(&graph);
+        let mut ordering = indices(&vertices, &ordering);
+
+        assert!(!multiple_insertion(
+          &mut Insertion::new(&digraph),
+          &mut ordering
+        ));
+      }
+    }
+  }
+}
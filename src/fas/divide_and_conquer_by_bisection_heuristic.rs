@@ -1,4 +1,5 @@
-use crate::bisection::stochastic_evolution::StochasticEvolution;
+use crate::bisection::directed_bisection::Bisection;
+use crate::bisection::stochastic_evolution::{Parameters, StochasticEvolution};
 use crate::fas::feedback_arc_set::FeedbackArcSet;
 use crate::graph::hash_table::{Edge, HashTable};
 use crate::scc::strongly_connected_components::StronglyConnectedComponents;
@@ -15,45 +16,61 @@ set F is then computed as the union of a feedback set of F1 of G[V1 ], a feedbac
 input graph is quickly decomposed into smaller subgraphs either by function bisect or by
 function scc.
  */
-pub struct DivideAndConquerByBisectionHeuristic<'a> {
+/// Any Bisection can be used for function bisect, by default the stochastic evolution
+pub struct DivideAndConquerByBisectionHeuristic<'a, B: Bisection = StochasticEvolution> {
   pub graph: &'a HashTable,
+  pub bisection: B,
 }
 
 impl<'a> DivideAndConquerByBisectionHeuristic<'a> {
   pub fn new(graph: &'a HashTable) -> Self {
-    Self { graph }
+    Self::with_seed(graph, 0)
+  }
+
+  /// The same seed always gives the same feedback arc set
+  pub fn with_seed(graph: &'a HashTable, seed: u64) -> Self {
+    Self::with_bisection(graph, StochasticEvolution::new(Parameters::with_seed(seed)))
   }
 }
 
-impl<'a> FeedbackArcSet for DivideAndConquerByBisectionHeuristic<'a> {
-  fn feedback_arc_set(&self) -> HashSet<Edge> {
-    let sc_components = Tarjan::new(self.graph).strongly_connected_components();
-    debug_assert!(!sc_components.is_empty(), "No SCCs = {:?}", self.graph);
+impl<'a, B: Bisection> DivideAndConquerByBisectionHeuristic<'a, B> {
+  pub fn with_bisection(graph: &'a HashTable, bisection: B) -> Self {
+    Self { graph, bisection }
+  }
+
+  fn fas(&self, graph: &HashTable) -> HashSet<Edge> {
+    // A single vertex is only part of a cycle if it has a self-loop
+    if graph.order() <= 1 {
+      return graph.all_edges().into_iter().collect();
+    }
+
+    let sc_components = Tarjan::new(graph).strongly_connected_components();
     let mut fas = HashSet::new();
 
     if sc_components.len() == 1 {
-      let (v_1, v_2) = StochasticEvolution::new(self.graph).bisection();
-      /* Doesn't make sense, although proposed by paper, because recursion would not terminate (fas on a bisection of an scc is simply one arc)...
-            let graph_from_v_1 =
-              HashTable::from_graph(self.graph, &v_1.iter().cloned().collect::<Vec<_>>());
-            let graph_from_v_2 =
-              HashTable::from_graph(self.graph, &v_2.iter().cloned().collect::<Vec<_>>());
-            let fas_from_v_1 =
-              DivideAndConquerByBisectionHeuristic::new(&graph_from_v_1).feedback_arc_set();
-            let fas_from_v_2 =
-              DivideAndConquerByBisectionHeuristic::new(&graph_from_v_2).feedback_arc_set();
-
-            fas.extend(&fas_from_v_1);
-            fas.extend(&fas_from_v_2);
-      */
-      fas.extend(self.graph.edges_from_to(&v_2, &v_1));
+      let (mut v_1, mut v_2) = self.bisection.bisection(graph);
+      // Recursion only terminates, if both parts are smaller than G
+      if v_1.is_empty() || v_2.is_empty() {
+        let vertices = graph.vertices();
+        let (first_half, second_half) = vertices.split_at(vertices.len() / 2);
+        v_1 = first_half.iter().copied().collect();
+        v_2 = second_half.iter().copied().collect();
+      }
+
+      fas.extend(graph.edges_from_to(&v_2, &v_1));
+      // Removing L does not break the cycles inside of G[V1] and G[V2]
+      for part in [v_1, v_2] {
+        let graph_from_part = HashTable::from_graph(graph, &part.into_iter().collect::<Vec<_>>());
+        fas.extend(self.fas(&graph_from_part));
+      }
     } else {
       for scc in sc_components {
-        let graph_from_scc =
-          HashTable::from_graph(self.graph, &scc.iter().cloned().collect::<Vec<_>>());
-        let fas_from_scc =
-          DivideAndConquerByBisectionHeuristic::new(&graph_from_scc).feedback_arc_set();
-        fas.extend(fas_from_scc);
+        let scc = scc.into_iter().collect::<Vec<_>>();
+        if scc.len() == 1 && !graph.has_edge(scc[0], scc[0]) {
+          continue;
+        }
+        let graph_from_scc = HashTable::from_graph(graph, &scc);
+        fas.extend(self.fas(&graph_from_scc));
       }
     }
 
@@ -61,9 +78,45 @@ impl<'a> FeedbackArcSet for DivideAndConquerByBisectionHeuristic<'a> {
   }
 }
 
+impl<'a, B: Bisection> FeedbackArcSet for DivideAndConquerByBisectionHeuristic<'a, B> {
+  fn feedback_arc_set(&self) -> HashSet<Edge> {
+    self.fas(self.graph)
+  }
+}
+
 #[cfg(test)]
 mod tests {
-  // use super::*;
-  // use crate::fas::feedback_arc_set::tests::fas_tests;
-  // fas_tests!(DivideAndConquerByBisectionHeuristic, [h_001, h_025]);
+  use super::*;
+  use crate::bisection::fiduccia_mattheyses::FiducciaMattheyses;
+  use crate::bisection::kernighan_lin::KernighanLin;
+  use crate::bisection::multilevel::Multilevel;
+  use crate::fas::feedback_arc_set::tests::fas_tests;
+  use crate::tools::graphs::graph_from_file;
+
+  fas_tests!(DivideAndConquerByBisectionHeuristic, [h_001, h_025]);
+
+  fn assert_breaks_all_cycles<B: Bisection>(bisection: B) {
+    let graph = graph_from_file("h_001");
+    let fas =
+      DivideAndConquerByBisectionHeuristic::with_bisection(&graph, bisection).feedback_arc_set();
+
+    let mut acyclic_graph = graph.clone();
+    fas.into_iter().for_each(|e| acyclic_graph.remove_edge(e));
+    assert!(!acyclic_graph.is_cyclic());
+  }
+
+  #[test]
+  fn works_with_fiduccia_mattheyses() {
+    assert_breaks_all_cycles(FiducciaMattheyses::default());
+  }
+
+  #[test]
+  fn works_with_kernighan_lin() {
+    assert_breaks_all_cycles(KernighanLin::default());
+  }
+
+  #[test]
+  fn works_with_multilevel() {
+    assert_breaks_all_cycles(Multilevel::default());
+  }
 }
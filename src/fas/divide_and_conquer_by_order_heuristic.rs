@@ -1,7 +1,8 @@
 use crate::fas::feedback_arc_set::FeedbackArcSet;
 use crate::graph::hash_table::{Edge, HashTable, VertexId};
-use crate::ordering::topological_sort::{leftward_edges, TopologicalSort};
-use std::collections::HashSet;
+use crate::ordering::topological_sort::leftward_edges;
+use crate::ordering::vertex_ordering::VertexOrdering;
+use std::collections::{HashMap, HashSet};
 
 /*
 Another heuristic by Eades, Smyth and Lin (ESL) (1989) finds a feedback arc set of
@@ -29,55 +30,166 @@ pub struct DivideAndConquerByOrderHeuristic<'a> {
 }
 
 impl<'a> DivideAndConquerByOrderHeuristic<'a> {
-  fn new(graph: &'a HashTable) -> Self {
+  pub fn new(graph: &'a HashTable) -> Self {
     Self { graph }
   }
 }
 
 impl FeedbackArcSet for DivideAndConquerByOrderHeuristic<'_> {
   fn feedback_arc_set(&self) -> HashSet<Edge> {
-    let ordering = order(self.graph.clone());
-    debug_assert_eq!(self.graph.vertices().len(), ordering.len());
-
-    leftward_edges(self.graph, ordering)
+    leftward_edges(self.graph, self.vertex_ordering())
   }
 }
 
-fn order(mut g: HashTable) -> Vec<VertexId> {
-  let s;
-  let edge_count = g.edge_count();
-  let sorted = TopologicalSort::new(&g).sort_by_indegree_asc();
+impl VertexOrdering for DivideAndConquerByOrderHeuristic<'_> {
+  fn vertex_ordering(&self) -> Vec<VertexId> {
+    let vertices = self.graph.vertices();
+    let mut order = Order::new(self.graph, &vertices);
+    let mut s = (0..vertices.len()).collect::<Vec<_>>();
+    order.order(&mut s);
+    debug_assert_eq!(vertices.len(), s.len());
+
+    s.into_iter().map(|i| vertices[i]).collect()
+  }
+}
 
-  if g.edge_count() == 0 {
-    s = g.vertices();
-  } else if edge_count % 2 == 1 {
-    let v = sorted.first().unwrap();
-    g.remove_vertex(*v);
+/*
+All subgraphs of order(G) are induced by the vertices of a range of one shared vertex sequence,
+which order(G) rearranges in place. Every subgraph gets a new label, and the predecessor lists of
+its vertices are reduced to the predecessors with the same label, so they always hold the arcs of
+the current subgraph and the indegree is their length. A call of order on a subgraph G(V', E') costs
+O(|V'| log |V'| + |E'|) for sorting by indegree and reducing the lists. Ties in indegree are broken
+by the vertex index, i.e. by vertex id.
+ */
+struct Order {
+  predecessors: Vec<Vec<usize>>,
+  label: Vec<usize>,
+  next_label: usize,
+}
 
-    let mut s1 = order(g.clone());
-    s1.insert(0, *v);
+impl Order {
+  fn new(graph: &HashTable, vertices: &[VertexId]) -> Self {
+    let index = vertices
+      .iter()
+      .enumerate()
+      .map(|(i, v)| (*v, i))
+      .collect::<HashMap<_, _>>();
+    let mut predecessors = vec![vec![]; vertices.len()];
+    for (source, destination) in graph.all_edges() {
+      predecessors[index[&destination]].push(index[&source]);
+    }
 
-    s = s1;
-  } else {
-    let first_half = &sorted[0..(sorted.len() / 2)];
-    let second_half = &sorted[(sorted.len() / 2)..sorted.len()];
+    Self {
+      predecessors,
+      label: vec![0; vertices.len()],
+      next_label: 1,
+    }
+  }
 
-    let g1 = HashTable::from_graph(&g, first_half);
-    let g2 = HashTable::from_graph(&g, second_half);
+  fn relabel(&mut self, s: &[usize]) {
+    for i in s {
+      self.label[*i] = self.next_label;
+    }
+    self.next_label += 1;
+  }
 
-    let mut s1 = order(g1);
-    let s2 = order(g2);
+  // Removes the predecessors that are no longer in the same subgraph, after s was relabeled
+  fn restrict(&mut self, s: &[usize]) {
+    for i in s {
+      let label = self.label[*i];
+      let mut predecessors = std::mem::take(&mut self.predecessors[*i]);
+      predecessors.retain(|p| self.label[*p] == label);
+      self.predecessors[*i] = predecessors;
+    }
+  }
 
-    s1.extend(s2);
-    s = s1;
+  // Indegree of vertex i in its subgraph
+  fn indegree(&self, i: usize) -> usize {
+    self.predecessors[i].len()
   }
 
-  s
+  fn order(&mut self, s: &mut [usize]) {
+    let mut indegrees = s
+      .iter()
+      .map(|i| (self.indegree(*i), *i))
+      .collect::<Vec<_>>();
+
+    if indegrees.iter().all(|(indegree, _)| *indegree == 0) {
+      s.sort_unstable();
+    } else if s.len() % 2 == 1 {
+      let (position, _) = indegrees
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, indegree)| **indegree)
+        .unwrap();
+      s.swap(0, position);
+
+      let (v, s1) = s.split_first_mut().unwrap();
+      self.relabel(&[*v]);
+      self.relabel(s1);
+      self.restrict(s1);
+      self.order(s1);
+    } else {
+      indegrees.sort_unstable();
+      for (slot, (_, i)) in s.iter_mut().zip(indegrees) {
+        *slot = i;
+      }
+
+      let (s1, s2) = s.split_at_mut(s.len() / 2);
+      self.relabel(s1);
+      self.relabel(s2);
+      self.restrict(s1);
+      self.restrict(s2);
+      self.order(s1);
+      self.order(s2);
+    }
+  }
 }
 
 #[cfg(test)]
 mod tests {
   use super::*;
   use crate::fas::feedback_arc_set::tests::fas_tests;
+  use crate::tools::graphs::{graph_from_wikipedia_scc, graph_with_simple_clique};
   fas_tests!(DivideAndConquerByOrderHeuristic, [h_001, h_025]);
+
+  #[test]
+  fn orders_simple_clique() {
+    // Odd: 0 has minimal indegree (ties by id), then {1, 2} is split by indegree
+    let clique = graph_with_simple_clique();
+
+    assert_eq!(
+      DivideAndConquerByOrderHeuristic::new(&clique).vertex_ordering(),
+      vec![0, 1, 2]
+    );
+  }
+
+  #[test]
+  fn orders_odd_graph() {
+    let graph = HashTable::from_edges(&[(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 3), (1, 5)]);
+    // Odd: indegrees 1: 1, 2: 1, 3: 2, 4: 1, 5: 2, so 1 comes first.
+    // Even: in G - 1 indegrees 2: 0, 3: 2, 4: 1, 5: 1, so G1 = {2, 4} without arcs and G2 = {5, 3}
+    let ordering = DivideAndConquerByOrderHeuristic::new(&graph).vertex_ordering();
+
+    assert_eq!(ordering, vec![1, 2, 4, 5, 3]);
+    assert_eq!(
+      leftward_edges(&graph, ordering),
+      HashSet::from([(3, 1), (3, 4)])
+    );
+  }
+
+  #[test]
+  fn orders_wikipedia_scc() {
+    // Even: indegrees 1: 1, 2: 1, 3: 2, 4: 2, 5: 1, 6: 3, 7: 3, 8: 1, so G1 = {1, 2, 5, 8} and G2 = {3, 4, 6, 7}.
+    // G1: indegrees 1: 1, 2: 1, 5: 1, 8: 0, so {8, 1} (no arcs) and {2, 5} (2 → 5).
+    // G2: indegrees 3: 1, 4: 1, 6: 1, 7: 2, so {3, 4} and {6, 7}.
+    let graph = graph_from_wikipedia_scc();
+    let ordering = DivideAndConquerByOrderHeuristic::new(&graph).vertex_ordering();
+
+    assert_eq!(ordering, vec![1, 8, 2, 5, 3, 4, 6, 7]);
+    assert_eq!(
+      leftward_edges(&graph, ordering),
+      HashSet::from([(5, 1), (4, 3), (4, 8), (7, 6)])
+    );
+  }
 }
@@ -35,9 +35,7 @@ pub(crate) mod tests {
           #[test]
           fn [<works_on_ $file_name>]() {
             let cyclic_graph = crate::tools::graphs::graph_from_file(stringify!($file_name));
-            let algorithm = $algo {
-              graph: &cyclic_graph,
-            };
+            let algorithm = $algo::new(&cyclic_graph);
 
             test_feedback_arc_set(&algorithm, &cyclic_graph);
           }
@@ -49,9 +47,7 @@ pub(crate) mod tests {
         let mut cyclic_graph = crate::tools::graphs::graph_from_wikipedia_scc();
         assert!(cyclic_graph.is_cyclic());
 
-        let algorithm = $algo {
-              graph: &cyclic_graph,
-            };
+        let algorithm = $algo::new(&cyclic_graph);
         let fas = algorithm.feedback_arc_set();
 
         fas.into_iter().for_each(|e| cyclic_graph.remove_edge(e));
@@ -63,7 +59,7 @@ pub(crate) mod tests {
       fn works_on_simple_clique() {
         let edges = [(0, 1), (1, 2), (2, 0)];
         let clique = HashTable::from_edges(&edges);
-        let fas = $algo { graph: &clique }.feedback_arc_set();
+        let fas = $algo::new(&clique).feedback_arc_set();
 
         assert_eq!(fas.len(), 1);
         assert!(fas.is_subset(&HashSet::from(edges)));
@@ -72,7 +68,7 @@ pub(crate) mod tests {
       #[test]
       fn works_on_multiple_cliques() {
         let clique = crate::tools::graphs::graph_with_multiple_cliques();
-        let algorithm = $algo { graph: &clique };
+        let algorithm = $algo::new(&clique);
         test_feedback_arc_set(&algorithm, &clique);
       }
     };
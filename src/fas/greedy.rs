@@ -14,9 +14,11 @@ while G != ∅ do
 s <- s1s2.
 */
 
-use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashSet, VecDeque};
 
 use crate::graph::hash_table::{Direction, Edge, HashTable, VertexId};
+use crate::ordering::topological_sort::leftward_edges;
+use crate::ordering::vertex_ordering::VertexOrdering;
 
 use super::feedback_arc_set::FeedbackArcSet;
 
@@ -27,14 +29,21 @@ pub struct GreedyHeuristic<'a> {
 
 impl<'a> GreedyHeuristic<'a> {
   /// Create a new instance of the greedy heuristic algorithm with a reference to the graph
-  fn new(graph: &'a HashTable) -> Self {
+  pub fn new(graph: &'a HashTable) -> Self {
     Self { graph }
   }
 }
 
 impl<'a> FeedbackArcSet for GreedyHeuristic<'a> {
-  /// compute the feedback arc set of the referenced
+  /// compute the feedback arc set of the referenced graph from the leftward arcs of the vertex sequence s
   fn feedback_arc_set(&self) -> HashSet<Edge> {
+    leftward_edges(self.graph, self.vertex_ordering())
+  }
+}
+
+impl<'a> VertexOrdering for GreedyHeuristic<'a> {
+  /// compute the vertex sequence s of the referenced graph
+  fn vertex_ordering(&self) -> Vec<VertexId> {
     // Create a container to hold the calculated fas
     let mut container = FasContainer::new(self.graph);
 
@@ -64,28 +73,15 @@ impl<'a> FeedbackArcSet for GreedyHeuristic<'a> {
         .fas_nodes
         .iter()
         .max_by(|(_, x), (_, y)| x.delta.cmp(&y.delta))
-        .map(|(vertex_id, _)| vertex_id.clone())
+        .map(|(vertex_id, _)| *vertex_id)
       {
         s1.push_back(maximum_delta);
         container.update_fas_nodes(maximum_delta)
       }
     }
 
-    // concadenate the two lists as described in the paper and set the index to the vertex id and the value to the enumeration
-    let s = s1
-      .into_iter()
-      .chain(s2)
-      .enumerate()
-      .map(|(idx, node_idx)| (node_idx, idx))
-      .collect::<HashMap<VertexId, usize>>();
-
-    // get all outgoing edges of the graph and map the source and target index to the hashmap s, so we can detect if the arc is a left bound arc
-    self
-      .graph
-      .all_edges()
-      .into_iter()
-      .filter(|(source_idx, target_idx)| s[source_idx] >= s[target_idx])
-      .collect()
+    // concadenate the two lists as described in the paper
+    s1.into_iter().chain(s2).collect()
   }
 }
 
@@ -219,4 +215,23 @@ mod tests {
   use crate::fas::feedback_arc_set::tests::fas_tests;
 
   fas_tests!(GreedyHeuristic, [h_001, h_025]);
+
+  #[test]
+  fn does_not_regress_on_bundled_instances() {
+    let manifest = crate::tools::manifest::Manifest::bundled().unwrap();
+
+    for entry in manifest.entries() {
+      let graph = crate::tools::graphs::graph_from_file(&entry.instance);
+      let fas = GreedyHeuristic::new(&graph).feedback_arc_set();
+
+      assert!(
+        fas.len() <= entry.max_accepted_fas_size(),
+        "{}: fas = {}, best known = {}, max accepted = {}",
+        entry.instance,
+        fas.len(),
+        entry.fas_size,
+        entry.max_accepted_fas_size()
+      );
+    }
+  }
 }
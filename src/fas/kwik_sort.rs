@@ -0,0 +1,239 @@
+/*
+KwikSort by Ailon, Charikar and Newman (Aggregating inconsistent information: ranking and
+clustering, 2008), a randomized 3-approximation for the FAS of tournaments:
+
+KwikSort(V)
+    if |V| ≤ exact_cutoff then return the optimal ordering of V (see subset_dp)
+    choose a pivot p ∈ V
+    L := {v ∈ V | v → p}; R := {v ∈ V | p → v}
+    return KwikSort(L), p, KwikSort(R)
+
+The pivot is either chosen uniformly at random, or deterministically as the vertex of median
+score (number of successors in V), which splits V as evenly as possible.
+
+In a tournament every other vertex is in L or R. If the graph is not a tournament, a vertex can
+be joined to p by no arc or by a 2-cycle. Such a vertex goes to R, unless it has more arcs to p
+than from p. The approximation guarantee is lost in this case, so the ordering is afterwards
+refined by sifting (see multilevel_heuristic), which never adds leftward arcs.
+ */
+
+use crate::bisection::weighted_digraph::WeightedDigraph;
+use crate::fas::feedback_arc_set::FeedbackArcSet;
+use crate::fas::multilevel_heuristic::sift;
+use crate::fas::subset_dp::{optimal_ordering, MAX_ORDER};
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use crate::ordering::topological_sort::leftward_edges;
+use crate::ordering::vertex_ordering::VertexOrdering;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Pivot {
+  Random,
+  /// The vertex with the median number of successors in its part, ties are broken by index
+  Median,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parameters {
+  pub pivot: Pivot,
+  /// Parts with at most this many vertices are ordered optimally by the subset DP
+  pub exact_cutoff: usize,
+  /// Upper bound for the sifting passes if the graph is not a tournament
+  pub max_passes: usize,
+  /// The same seed always gives the same feedback arc set
+  pub seed: u64,
+}
+
+impl Default for Parameters {
+  fn default() -> Self {
+    Self {
+      pivot: Pivot::Random,
+      exact_cutoff: 10,
+      max_passes: 10,
+      seed: 0,
+    }
+  }
+}
+
+impl Parameters {
+  /// The default parameters with another seed
+  pub fn with_seed(seed: u64) -> Self {
+    Self {
+      seed,
+      ..Self::default()
+    }
+  }
+}
+
+pub struct KwikSort<'a> {
+  pub graph: &'a HashTable,
+  pub parameters: Parameters,
+}
+
+impl<'a> KwikSort<'a> {
+  pub fn new(graph: &'a HashTable) -> Self {
+    Self::with_parameters(graph, Parameters::default())
+  }
+
+  pub fn with_seed(graph: &'a HashTable, seed: u64) -> Self {
+    Self::with_parameters(graph, Parameters::with_seed(seed))
+  }
+
+  pub fn with_parameters(graph: &'a HashTable, parameters: Parameters) -> Self {
+    assert!(parameters.exact_cutoff <= MAX_ORDER);
+    Self { graph, parameters }
+  }
+}
+
+/// Whether every pair of distinct vertices is joined by exactly one arc (self-loops are ignored)
+pub fn is_tournament(graph: &HashTable) -> bool {
+  let n = graph.order();
+  let arcs = graph.all_edges();
+  let loops = arcs.iter().filter(|(u, v)| u == v).count();
+  arcs.len() - loops == n * (n.saturating_sub(1)) / 2
+    && arcs.iter().all(|(u, v)| u == v || !graph.has_edge(*v, *u))
+}
+
+impl<'a> VertexOrdering for KwikSort<'a> {
+  fn vertex_ordering(&self) -> Vec<VertexId> {
+    let (digraph, vertices) = WeightedDigraph::from_graph(self.graph);
+    let mut rng = StdRng::seed_from_u64(self.parameters.seed);
+    let mut ordering = (0..digraph.order()).collect::<Vec<_>>();
+    // w(v → p) - w(p → v) for the current pivot p
+    let mut towards_pivot = vec![0; digraph.order()];
+
+    // Every part is sorted in place, so the parts can be processed in any order
+    let mut parts = vec![(0, ordering.len())];
+    while let Some((start, end)) = parts.pop() {
+      let part = &mut ordering[start..end];
+      if part.len() <= self.parameters.exact_cutoff {
+        let optimal = optimal_ordering(&digraph, part);
+        part.copy_from_slice(&optimal);
+        continue;
+      }
+
+      let p = match self.parameters.pivot {
+        Pivot::Random => part[rng.gen_range(0..part.len())],
+        Pivot::Median => median_pivot(&digraph, part),
+      };
+      for (v, weight) in &digraph.predecessors[p] {
+        towards_pivot[*v] += weight;
+      }
+      for (v, weight) in &digraph.successors[p] {
+        towards_pivot[*v] -= weight;
+      }
+
+      // Stable partition into L, p and R
+      let (left, right): (Vec<usize>, Vec<usize>) = part
+        .iter()
+        .filter(|v| **v != p)
+        .partition(|v| towards_pivot[**v] > 0);
+      let arcs = digraph.predecessors[p].iter().chain(&digraph.successors[p]);
+      arcs.for_each(|(v, _)| towards_pivot[*v] = 0);
+
+      let split = start + left.len();
+      part[..left.len()].copy_from_slice(&left);
+      part[left.len()] = p;
+      part[left.len() + 1..].copy_from_slice(&right);
+      parts.push((start, split));
+      parts.push((split + 1, end));
+    }
+
+    if !is_tournament(self.graph) {
+      sift(&digraph, &mut ordering, self.parameters.max_passes);
+    }
+    ordering.into_iter().map(|i| vertices[i]).collect()
+  }
+}
+
+impl<'a> FeedbackArcSet for KwikSort<'a> {
+  fn feedback_arc_set(&self) -> HashSet<Edge> {
+    leftward_edges(self.graph, self.vertex_ordering())
+  }
+}
+
+fn median_pivot(graph: &WeightedDigraph, part: &[usize]) -> usize {
+  let mut in_part = HashSet::with_capacity(part.len());
+  in_part.extend(part.iter().copied());
+  let mut scores = part
+    .iter()
+    .map(|v| {
+      let successors = graph.successors[*v]
+        .iter()
+        .filter(|(u, _)| in_part.contains(u));
+      (successors.map(|(_, weight)| weight).sum::<i64>(), *v)
+    })
+    .collect::<Vec<_>>();
+  scores.sort_unstable();
+  scores[scores.len() / 2].1
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::fas::feedback_arc_set::tests::fas_tests;
+  use crate::fas::subset_dp::SubsetDp;
+  use crate::generator::tournament::{near_transitive_tournament, random_tournament};
+
+  fas_tests!(KwikSort, [h_001, h_025]);
+
+  #[test]
+  fn detects_tournaments() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    assert!(is_tournament(&random_tournament(20, &mut rng)));
+    assert!(is_tournament(&HashTable::complete(5)));
+    assert!(!is_tournament(&HashTable::from_edges(&[(0, 1), (1, 0)])));
+    assert!(!is_tournament(&HashTable::from_edges(&[(0, 1), (1, 2)])));
+  }
+
+  #[test]
+  fn sorts_transitive_tournament() {
+    let graph = near_transitive_tournament(100, 0.0, &mut StdRng::seed_from_u64(0));
+    let median = Parameters {
+      pivot: Pivot::Median,
+      ..Parameters::default()
+    };
+
+    assert!(KwikSort::new(&graph).feedback_arc_set().is_empty());
+    assert_eq!(
+      KwikSort::with_parameters(&graph, median).vertex_ordering(),
+      (0..100).collect::<Vec<_>>()
+    );
+  }
+
+  #[test]
+  fn is_within_factor_three_on_tournaments() {
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..5 {
+      let graph = random_tournament(18, &mut rng);
+      let optimum = SubsetDp::new(&graph).feedback_arc_set().len();
+      let parameters = Parameters {
+        exact_cutoff: 4,
+        ..Parameters::default()
+      };
+      let mean = (0..10)
+        .map(|seed| {
+          KwikSort::with_parameters(&graph, Parameters { seed, ..parameters })
+            .feedback_arc_set()
+            .len()
+        })
+        .sum::<usize>() as f64
+        / 10.0;
+
+      assert!(mean <= 3.0 * optimum as f64);
+    }
+  }
+
+  #[test]
+  fn exact_cutoff_gives_optimum_on_small_graphs() {
+    let graph = random_tournament(10, &mut StdRng::seed_from_u64(2));
+
+    assert_eq!(
+      KwikSort::new(&graph).feedback_arc_set().len(),
+      SubsetDp::new(&graph).feedback_arc_set().len()
+    );
+  }
+}
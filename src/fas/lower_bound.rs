@@ -0,0 +1,212 @@
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use std::collections::{HashSet, VecDeque};
+
+/*
+Lower bounds by packing arc-disjoint cycles. Every FAS contains at least one arc of every cycle,
+so k arc-disjoint cycles prove that no FAS has less than k arcs.
+
+two_cycle_bound(G):
+    C := self-loops and 2-cycles u ⇄ v of G          {arc-disjoint by definition}
+
+cycle_packing_bound(G):
+    C := two_cycle_bound(G); remove the arcs of C from G
+    L := 3
+    repeat
+        for every vertex s
+            while the shortest cycle through s has at most L arcs      {BFS from s}
+                add it to C and remove its arcs from G
+        L := length of the shortest cycle skipped in this pass
+    until G is acyclic
+return |C| with the cycles C as certificate
+
+Packing short cycles first leaves more arcs for further cycles.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct LowerBound {
+  pub bound: usize,
+  /// Arc-disjoint cycles as vertex sequences, the arcs are v0 → v1 → ... → v0
+  pub cycles: Vec<Vec<VertexId>>,
+}
+
+impl LowerBound {
+  fn new(cycles: Vec<Vec<VertexId>>) -> Self {
+    Self {
+      bound: cycles.len(),
+      cycles,
+    }
+  }
+
+  /// Checks the certificate: the cycles consist of arcs of the graph and share no arc
+  pub fn verify(&self, graph: &HashTable) -> Result<(), String> {
+    if self.bound != self.cycles.len() {
+      return Err(format!(
+        "Bound {} is not certified by {} cycles",
+        self.bound,
+        self.cycles.len()
+      ));
+    }
+    let mut used = HashSet::new();
+    for cycle in &self.cycles {
+      if cycle.is_empty() {
+        return Err("Empty cycle".to_string());
+      }
+      for (i, u) in cycle.iter().enumerate() {
+        let e = (*u, cycle[(i + 1) % cycle.len()]);
+        if !graph.has_edge(e.0, e.1) {
+          return Err(format!("{e:?} is not an arc of the graph"));
+        }
+        if !used.insert(e) {
+          return Err(format!("{e:?} is used by two cycles"));
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Self-loops and 2-cycles, each forces one of its arcs into every FAS
+pub fn two_cycle_bound(graph: &HashTable) -> LowerBound {
+  let cycles = graph
+    .all_edges()
+    .into_iter()
+    .filter(|(u, v)| u == v || (u < v && graph.has_edge(*v, *u)))
+    .map(|(u, v)| if u == v { vec![u] } else { vec![u, v] })
+    .collect();
+  LowerBound::new(cycles)
+}
+
+/// Extends the 2-cycles by a greedy packing of shortest cycles, at least as large as two_cycle_bound
+pub fn cycle_packing_bound(graph: &HashTable) -> LowerBound {
+  let mut cycles = two_cycle_bound(graph).cycles;
+  let packed = cycles
+    .iter()
+    .flat_map(|cycle| (0..cycle.len()).map(move |i| (cycle[i], cycle[(i + 1) % cycle.len()])))
+    .collect::<HashSet<Edge>>();
+
+  let vertices = graph.vertices();
+  let mut successors = vertices
+    .iter()
+    .map(|u| {
+      graph
+        .neighborhood(u)
+        .iter()
+        .filter(|v| !packed.contains(&(*u, **v)))
+        .map(|v| vertices.binary_search(v).unwrap())
+        .collect::<Vec<_>>()
+    })
+    .collect::<Vec<_>>();
+
+  let mut max_length = 3;
+  loop {
+    let mut shortest_skipped = None::<usize>;
+    for s in 0..vertices.len() {
+      while let Some(cycle) = shortest_cycle_through(&successors, s) {
+        if cycle.len() > max_length {
+          shortest_skipped = Some(shortest_skipped.map_or(cycle.len(), |l| l.min(cycle.len())));
+          break;
+        }
+        for (i, u) in cycle.iter().enumerate() {
+          let v = cycle[(i + 1) % cycle.len()];
+          successors[*u].retain(|w| *w != v);
+        }
+        cycles.push(cycle.into_iter().map(|i| vertices[i]).collect());
+      }
+    }
+    match shortest_skipped {
+      Some(length) => max_length = length,
+      None => break,
+    }
+  }
+  LowerBound::new(cycles)
+}
+
+/// Breadth-first search from s, the first arc back to s closes a shortest cycle
+fn shortest_cycle_through(successors: &[Vec<usize>], s: usize) -> Option<Vec<usize>> {
+  let mut parent = vec![usize::MAX; successors.len()];
+  parent[s] = s;
+  let mut queue = VecDeque::from([s]);
+  while let Some(u) = queue.pop_front() {
+    for &v in &successors[u] {
+      if v == s {
+        let mut cycle = vec![u];
+        while *cycle.last().unwrap() != s {
+          cycle.push(parent[*cycle.last().unwrap()]);
+        }
+        cycle.reverse();
+        return Some(cycle);
+      }
+      if parent[v] == usize::MAX {
+        parent[v] = u;
+        queue.push_back(v);
+      }
+    }
+  }
+  None
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::fas::feedback_arc_set::FeedbackArcSet;
+  use crate::fas::greedy::GreedyHeuristic;
+  use crate::fas::lower_bound::{cycle_packing_bound, two_cycle_bound};
+  use crate::graph::hash_table::HashTable;
+  use crate::tools::graphs::{graph_from_file, graph_from_wikipedia_scc};
+
+  #[test]
+  fn counts_self_loops_and_two_cycles() {
+    let graph = HashTable::from_edges(&[(0, 0), (0, 1), (1, 0), (1, 2), (2, 1), (2, 3)]);
+    let lower_bound = two_cycle_bound(&graph);
+
+    assert_eq!(lower_bound.bound, 3);
+    assert_eq!(lower_bound.cycles, vec![vec![0], vec![0, 1], vec![1, 2]]);
+    assert_eq!(lower_bound.verify(&graph), Ok(()));
+  }
+
+  #[test]
+  fn packs_arc_disjoint_shortest_cycles() {
+    // Two triangles sharing the arc 0 → 1 and a disjoint square
+    let graph = HashTable::from_edges(&[
+      (0, 1),
+      (1, 2),
+      (2, 0),
+      (1, 3),
+      (3, 0),
+      (4, 5),
+      (5, 6),
+      (6, 7),
+      (7, 4),
+    ]);
+    let lower_bound = cycle_packing_bound(&graph);
+
+    assert_eq!(lower_bound.bound, 2);
+    assert_eq!(lower_bound.cycles, vec![vec![0, 1, 2], vec![4, 5, 6, 7]]);
+    assert_eq!(lower_bound.verify(&graph), Ok(()));
+  }
+
+  #[test]
+  fn bounds_heuristic_solutions() {
+    for graph in [graph_from_wikipedia_scc(), graph_from_file("h_001")] {
+      let two_cycles = two_cycle_bound(&graph);
+      let packing = cycle_packing_bound(&graph);
+      let fas = GreedyHeuristic::new(&graph).feedback_arc_set();
+
+      assert_eq!(packing.verify(&graph), Ok(()));
+      assert!(two_cycles.bound <= packing.bound);
+      assert!(packing.bound <= fas.len());
+      assert!(packing.bound > 0);
+    }
+  }
+
+  #[test]
+  fn verify_detects_shared_arcs() {
+    let graph = HashTable::from_edges(&[(0, 1), (1, 0)]);
+    let mut lower_bound = two_cycle_bound(&graph);
+    lower_bound.cycles.push(vec![1, 0]);
+    lower_bound.bound = 2;
+    assert!(lower_bound.verify(&graph).is_err());
+
+    lower_bound.cycles = vec![vec![0, 2]];
+    lower_bound.bound = 1;
+    assert!(lower_bound.verify(&graph).is_err());
+  }
+}
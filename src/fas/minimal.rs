@@ -0,0 +1,99 @@
+use crate::fas::feedback_arc_set::FeedbackArcSet;
+use crate::graph::hash_table::{Edge, HashTable};
+use crate::ordering::incremental_topological_order::IncrementalTopologicalOrder;
+use std::collections::HashSet;
+
+/*
+Post-processing that makes any feedback arc set F inclusion-minimal:
+
+D := G - F with an incrementally maintained topological order
+for every arc u → v of F
+    if v does not reach u in D then
+        insert u → v into D and remove it from F
+return F
+
+An arc that closes a cycle still does so after further insertions, so a single pass suffices.
+The arcs are processed in ascending order, so the result does not depend on the order of the HashSet.
+ */
+pub fn make_minimal(graph: &HashTable, fas: &HashSet<Edge>) -> HashSet<Edge> {
+  let mut acyclic_graph = graph.clone();
+  fas.iter().for_each(|e| acyclic_graph.remove_edge(*e));
+  let mut order = IncrementalTopologicalOrder::new(&acyclic_graph)
+    .expect("Not a feedback arc set, remaining graph is cyclic");
+
+  let mut arcs = fas.iter().copied().collect::<Vec<_>>();
+  arcs.sort_unstable();
+  arcs
+    .into_iter()
+    .filter(|e| !order.try_add_edge(*e))
+    .collect()
+}
+
+/// Wraps any feedback arc set algorithm, so that its result is made minimal by make_minimal
+pub struct Minimal<'a, A: FeedbackArcSet> {
+  pub graph: &'a HashTable,
+  pub algorithm: A,
+}
+
+impl<'a, A: FeedbackArcSet> Minimal<'a, A> {
+  pub fn new(graph: &'a HashTable, algorithm: A) -> Self {
+    Self { graph, algorithm }
+  }
+}
+
+impl<'a, A: FeedbackArcSet> FeedbackArcSet for Minimal<'a, A> {
+  fn feedback_arc_set(&self) -> HashSet<Edge> {
+    make_minimal(self.graph, &self.algorithm.feedback_arc_set())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::fas::feedback_arc_set::FeedbackArcSet;
+  use crate::fas::minimal::{make_minimal, Minimal};
+  use crate::fas::simple_heuristic::SimpleHeuristic;
+  use crate::graph::hash_table::{Edge, HashTable};
+  use crate::tools::graphs::{graph_from_file, graph_from_wikipedia_scc};
+  use std::collections::HashSet;
+
+  fn assert_is_minimal(graph: &HashTable, fas: &HashSet<Edge>) {
+    let mut acyclic_graph = graph.clone();
+    fas.iter().for_each(|e| acyclic_graph.remove_edge(*e));
+    assert!(!acyclic_graph.is_cyclic());
+
+    for e in fas {
+      let mut with_e = acyclic_graph.clone();
+      with_e.add_edge(*e);
+      assert!(with_e.is_cyclic(), "{e:?} is redundant");
+    }
+  }
+
+  #[test]
+  fn all_arcs_become_minimal() {
+    let graph = graph_from_wikipedia_scc();
+    let all_arcs = graph.all_edges().into_iter().collect::<HashSet<_>>();
+    let fas = make_minimal(&graph, &all_arcs);
+
+    assert!(fas.is_subset(&all_arcs));
+    assert_is_minimal(&graph, &fas);
+  }
+
+  #[test]
+  fn keeps_self_loops() {
+    let graph = HashTable::from_edges(&[(0, 0), (0, 1)]);
+    let fas = make_minimal(&graph, &HashSet::from([(0, 0), (0, 1)]));
+
+    assert_eq!(fas, HashSet::from([(0, 0)]));
+  }
+
+  #[test]
+  fn wraps_simple_heuristic() {
+    let graph = graph_from_file("h_001");
+    let simple = SimpleHeuristic::new(&graph).feedback_arc_set();
+    let fas = Minimal::new(&graph, SimpleHeuristic::new(&graph)).feedback_arc_set();
+
+    assert!(fas.len() <= simple.len());
+    assert!(fas.is_subset(&simple));
+    assert_is_minimal(&graph, &fas);
+  }
+}
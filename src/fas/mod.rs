@@ -1,5 +1,20 @@
+pub mod algorithm;
+pub mod bubble_sort;
+pub mod chanas_kobylanski;
 pub mod divide_and_conquer_by_bisection_heuristic;
 pub mod divide_and_conquer_by_order_heuristic;
 pub mod feedback_arc_set;
 pub mod greedy;
+pub mod kwik_sort;
+pub mod lower_bound;
+pub mod minimal;
+pub mod multilevel_heuristic;
+pub mod page_rank;
+pub mod scc_decomposition;
 pub mod simple_heuristic;
+pub mod simulated_annealing;
+pub mod solution;
+pub mod sort_fas;
+pub mod spectral;
+pub mod subset_dp;
+pub mod tabu_search;
@@ -0,0 +1,324 @@
+/*
+Multilevel heuristic on vertex orderings:
+
+G0 := G
+while Gi has more than coarsest_order vertices
+    match strongly tied vertex pairs of Gi (see tied_matching)
+    Gi+1 := Gi with every matched pair contracted to a single vertex (arc weights are added up)
+s := vertex sequence of the greedy heuristic on the coarsest graph, refined by sifting
+for every level i from coarsest to finest
+    expand s to Gi, the vertices of a pair are ordered by the heavier of their arcs
+    refine s on Gi by sifting
+return the leftward arcs of s
+
+Sifting moves a single vertex to the position, where the weight of its leftward arcs is minimal.
+Only the positions between its neighbors have to be compared, so a pass costs O(|E| log |V|).
+ */
+
+use crate::bisection::weighted_digraph::{coarsen, WeightedDigraph};
+use crate::fas::feedback_arc_set::FeedbackArcSet;
+use crate::fas::greedy::GreedyHeuristic;
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use crate::ordering::topological_sort::leftward_edges;
+use crate::ordering::vertex_ordering::VertexOrdering;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parameters {
+  /// Coarsening stops as soon as the graph has at most this many vertices
+  pub coarsest_order: usize,
+  /// Upper bound for the number of sifting passes on each level
+  pub max_passes: usize,
+  /// The same seed always gives the same feedback arc set
+  pub seed: u64,
+}
+
+impl Default for Parameters {
+  fn default() -> Self {
+    Self {
+      coarsest_order: 64,
+      max_passes: 10,
+      seed: 0,
+    }
+  }
+}
+
+impl Parameters {
+  /// The default parameters with another seed
+  pub fn with_seed(seed: u64) -> Self {
+    Self {
+      seed,
+      ..Self::default()
+    }
+  }
+}
+
+pub struct MultilevelHeuristic<'a> {
+  pub graph: &'a HashTable,
+  pub parameters: Parameters,
+}
+
+impl<'a> MultilevelHeuristic<'a> {
+  pub fn new(graph: &'a HashTable) -> Self {
+    Self::with_parameters(graph, Parameters::default())
+  }
+
+  pub fn with_seed(graph: &'a HashTable, seed: u64) -> Self {
+    Self::with_parameters(graph, Parameters::with_seed(seed))
+  }
+
+  pub fn with_parameters(graph: &'a HashTable, parameters: Parameters) -> Self {
+    Self { graph, parameters }
+  }
+}
+
+impl<'a> VertexOrdering for MultilevelHeuristic<'a> {
+  fn vertex_ordering(&self) -> Vec<VertexId> {
+    let (digraph, vertices) = WeightedDigraph::from_graph(self.graph);
+    let mut rng = StdRng::seed_from_u64(self.parameters.seed);
+
+    let levels = coarsen(
+      &digraph,
+      self.parameters.coarsest_order,
+      &mut rng,
+      tied_matching,
+    );
+
+    let coarsest = levels.last().map_or(&digraph, |level| &level.graph);
+    let mut ordering = greedy_ordering(coarsest);
+    sift(coarsest, &mut ordering, self.parameters.max_passes);
+
+    for i in (0..levels.len()).rev() {
+      let finer = if i == 0 {
+        &digraph
+      } else {
+        &levels[i - 1].graph
+      };
+      ordering = expand(finer, &levels[i].coarse, &ordering);
+      sift(finer, &mut ordering, self.parameters.max_passes);
+    }
+
+    ordering.into_iter().map(|i| vertices[i]).collect()
+  }
+}
+
+impl<'a> FeedbackArcSet for MultilevelHeuristic<'a> {
+  fn feedback_arc_set(&self) -> HashSet<Edge> {
+    leftward_edges(self.graph, self.vertex_ordering())
+  }
+}
+
+/*
+Matches every unmatched vertex u with the unmatched neighbor v it is most strongly tied to, i.e. with
+the maximum weight of arcs between u and v relative to sqrt(weighted degree(u) * weighted degree(v)).
+Without the normalization all pairs of an unweighted graph would be tied equally.
+ */
+fn tied_matching(graph: &WeightedDigraph, order: &[usize]) -> (Vec<usize>, usize) {
+  let degrees = (0..graph.order())
+    .map(|u| {
+      let arcs = graph.successors[u].iter().chain(&graph.predecessors[u]);
+      arcs.map(|(_, weight)| weight).sum::<i64>() as f64
+    })
+    .collect::<Vec<_>>();
+
+  let mut coarse = vec![usize::MAX; graph.order()];
+  let mut coarse_order = 0;
+  for &u in order {
+    if coarse[u] != usize::MAX {
+      continue;
+    }
+    let partner = graph
+      .connections(u)
+      .into_iter()
+      .filter(|(v, _)| coarse[*v] == usize::MAX)
+      .map(|(v, weight)| (v, weight as f64 / (degrees[u] * degrees[v]).sqrt()))
+      // Ties are broken by the index, because the order of a HashMap is random
+      .max_by(|(v, x), (w, y)| x.total_cmp(y).then(w.cmp(v)));
+    coarse[u] = coarse_order;
+    if let Some((v, _)) = partner {
+      coarse[v] = coarse_order;
+    }
+    coarse_order += 1;
+  }
+  (coarse, coarse_order)
+}
+
+// The greedy heuristic ignores the arc weights of the coarse graph
+fn greedy_ordering(graph: &WeightedDigraph) -> Vec<usize> {
+  let vertices = (0..graph.order() as VertexId).collect::<Vec<_>>();
+  let arcs = (0..graph.order())
+    .flat_map(|u| {
+      graph.successors[u]
+        .iter()
+        .map(move |(v, _)| (u as VertexId, *v as VertexId))
+    })
+    .collect::<Vec<_>>();
+  let coarse_graph = HashTable::from_vertices_and_edges(&vertices, &arcs);
+
+  GreedyHeuristic::new(&coarse_graph)
+    .vertex_ordering()
+    .into_iter()
+    .map(|v| v as usize)
+    .collect()
+}
+
+// Replaces every coarse vertex of the ordering by the vertices of the finer graph it contains
+fn expand(finer: &WeightedDigraph, coarse: &[usize], coarse_ordering: &[usize]) -> Vec<usize> {
+  let mut members = vec![vec![]; coarse_ordering.len()];
+  for (i, c) in coarse.iter().enumerate() {
+    members[*c].push(i);
+  }
+
+  coarse_ordering
+    .iter()
+    .flat_map(|c| {
+      let mut pair = std::mem::take(&mut members[*c]);
+      if let [u, v] = pair[..] {
+        if finer.arc_weight(v, u) > finer.arc_weight(u, v) {
+          pair.swap(0, 1);
+        }
+      }
+      pair
+    })
+    .collect()
+}
+
+/// Moves single vertices to the position with the least weight of leftward arcs, until a pass
+/// does not improve the ordering.
+pub(crate) fn sift(graph: &WeightedDigraph, ordering: &mut [usize], max_passes: usize) {
+  let mut position = vec![0.0; graph.order()];
+
+  for _ in 0..max_passes {
+    // Vertices between two others get the mean of their positions, so the rest does not have to move
+    for (p, i) in ordering.iter().enumerate() {
+      position[*i] = p as f64;
+    }
+
+    let mut improved = false;
+    for &v in ordering.iter() {
+      // (position of neighbor, weight change if v is moved behind it)
+      let mut neighbors = graph.successors[v]
+        .iter()
+        .map(|(u, weight)| (position[*u], *weight))
+        .chain(
+          graph.predecessors[v]
+            .iter()
+            .map(|(u, weight)| (position[*u], -*weight)),
+        )
+        .collect::<Vec<_>>();
+      if neighbors.is_empty() {
+        continue;
+      }
+      neighbors.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+      // In front of all neighbors, only the arcs from predecessors point leftwards
+      let mut cost = graph.predecessors[v].iter().map(|(_, w)| w).sum::<i64>();
+      let mut current_cost = cost;
+      let mut best = (cost, neighbors[0].0 - 1.0);
+      for (k, &(p, change)) in neighbors.iter().enumerate() {
+        cost += change;
+        if p < position[v] {
+          current_cost = cost;
+        }
+        // Between two neighbors at the same position there is no gap
+        if neighbors.get(k + 1).is_some_and(|(next, _)| *next == p) {
+          continue;
+        }
+        let gap = neighbors
+          .get(k + 1)
+          .map_or(p + 1.0, |(next, _)| (p + next) / 2.0);
+        if cost < best.0 && gap != p {
+          best = (cost, gap);
+        }
+      }
+
+      if best.0 < current_cost {
+        position[v] = best.1;
+        improved = true;
+      }
+    }
+
+    ordering.sort_by(|x, y| position[*x].total_cmp(&position[*y]));
+    if !improved {
+      break;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::fas::feedback_arc_set::tests::fas_tests;
+  use crate::fas::multilevel_heuristic::{expand, sift};
+  use crate::tools::graphs::graph_from_file;
+
+  fas_tests!(MultilevelHeuristic, [h_001, h_025]);
+
+  fn leftward_weight(graph: &WeightedDigraph, ordering: &[usize]) -> i64 {
+    let mut position = vec![0; graph.order()];
+    ordering
+      .iter()
+      .enumerate()
+      .for_each(|(p, i)| position[*i] = p);
+    (0..graph.order())
+      .flat_map(|u| graph.successors[u].iter().map(move |(v, w)| (u, *v, *w)))
+      .filter(|(u, v, _)| position[*v] < position[*u])
+      .map(|(_, _, w)| w)
+      .sum()
+  }
+
+  #[test]
+  fn sift_moves_vertex_behind_its_predecessors() {
+    // 0 → 1 → 2 → 3 and 0 → 3, but 3 is in front
+    let mut digraph = WeightedDigraph::with_vertex_weights(vec![1; 4]);
+    for (u, v) in [(0, 1), (1, 2), (2, 3), (0, 3)] {
+      digraph.add_arc(u, v, 1);
+    }
+    let mut ordering = vec![3, 0, 1, 2];
+
+    sift(&digraph, &mut ordering, 10);
+    assert_eq!(ordering, vec![0, 1, 2, 3]);
+  }
+
+  #[test]
+  fn sift_never_increases_leftward_weight() {
+    let (digraph, _) = WeightedDigraph::from_graph(&graph_from_file("h_001"));
+    let mut ordering = (0..digraph.order()).collect::<Vec<_>>();
+    let initial = leftward_weight(&digraph, &ordering);
+
+    sift(&digraph, &mut ordering, 10);
+    assert!(leftward_weight(&digraph, &ordering) < initial);
+    assert_eq!(ordering.len(), digraph.order());
+  }
+
+  #[test]
+  fn expand_orders_pairs_by_heavier_arc() {
+    let mut digraph = WeightedDigraph::with_vertex_weights(vec![1; 3]);
+    digraph.add_arc(1, 0, 2);
+    digraph.add_arc(0, 1, 1);
+
+    assert_eq!(expand(&digraph, &[0, 0, 1], &[1, 0]), vec![2, 1, 0]);
+  }
+
+  #[test]
+  fn improves_greedy_on_h_025() {
+    let graph = graph_from_file("h_025");
+
+    assert!(
+      MultilevelHeuristic::new(&graph).feedback_arc_set().len()
+        < GreedyHeuristic::new(&graph).feedback_arc_set().len()
+    );
+  }
+
+  #[test]
+  fn same_seed_gives_same_fas() {
+    let graph = graph_from_file("h_001");
+
+    assert_eq!(
+      MultilevelHeuristic::with_seed(&graph, 3).feedback_arc_set(),
+      MultilevelHeuristic::with_seed(&graph, 3).feedback_arc_set()
+    );
+  }
+}
@@ -0,0 +1,170 @@
+/*
+PageRankFAS by Geladaris, Lionakis and Tollis (Computing a feedback arc set using PageRank, 2022):
+
+F := self-loops of G
+S := strongly connected components of G - F with more than one vertex    {Tarjan}
+while S ≠ ∅
+    take a component C from S
+    rank the arcs of C by PageRank on the line digraph L(C)
+    remove the arc e with the highest rank from C; F := F ∪ {e}
+    S := S ∪ strongly connected components of C with more than one vertex
+return F
+
+The line digraph has a vertex for every arc of C and an arc from u → v to every v → w. It is never
+built: all arcs into v → w come from arcs into v, so one power iteration costs O(|E(C)|):
+    rank'(v → w) = (1 - damping) / |E(C)| + damping * Σ_{u → v} rank(u → v) / outdegree(v)
+ */
+
+use crate::fas::feedback_arc_set::FeedbackArcSet;
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use crate::scc::strongly_connected_components::cyclic_components;
+use std::collections::HashSet;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parameters {
+  /// Probability to follow an arc instead of jumping to a random one
+  pub damping: f64,
+  /// The power iteration stops as soon as the ranks change by less than this (L1 norm)
+  pub tolerance: f64,
+  /// Upper bound for the number of power iterations per ranking
+  pub max_iterations: usize,
+}
+
+impl Default for Parameters {
+  fn default() -> Self {
+    Self {
+      damping: 0.85,
+      tolerance: 1e-6,
+      max_iterations: 100,
+    }
+  }
+}
+
+pub struct PageRankHeuristic<'a> {
+  pub graph: &'a HashTable,
+  pub parameters: Parameters,
+}
+
+impl<'a> PageRankHeuristic<'a> {
+  pub fn new(graph: &'a HashTable) -> Self {
+    Self::with_parameters(graph, Parameters::default())
+  }
+
+  pub fn with_parameters(graph: &'a HashTable, parameters: Parameters) -> Self {
+    assert!((0.0..1.0).contains(&parameters.damping));
+    Self { graph, parameters }
+  }
+
+  /// The arc with the highest PageRank in the line digraph, ties are broken by the smaller arc
+  fn highest_ranked_arc(&self, component: &HashTable) -> Edge {
+    let vertices = component.vertices();
+    let index = |v: &VertexId| vertices.binary_search(v).unwrap();
+    let arcs = component
+      .all_edges()
+      .into_iter()
+      .map(|(u, v)| (index(&u), index(&v)))
+      .collect::<Vec<_>>();
+
+    let mut out_degree = vec![0; vertices.len()];
+    arcs.iter().for_each(|(u, _)| out_degree[*u] += 1);
+
+    let m = arcs.len() as f64;
+    let mut rank = vec![1.0 / m; arcs.len()];
+    let mut into = vec![0.0; vertices.len()];
+    for _ in 0..self.parameters.max_iterations {
+      into.iter_mut().for_each(|r| *r = 0.0);
+      for ((_, v), r) in arcs.iter().zip(&rank) {
+        into[*v] += r;
+      }
+
+      // The ranks of arcs out of a vertex without successors would be lost, so they are spread evenly
+      let dangling = arcs
+        .iter()
+        .zip(&rank)
+        .filter(|((_, v), _)| out_degree[*v] == 0)
+        .map(|(_, r)| r)
+        .sum::<f64>();
+      let base = (1.0 - self.parameters.damping + self.parameters.damping * dangling) / m;
+
+      let mut change = 0.0;
+      for ((u, _), r) in arcs.iter().zip(rank.iter_mut()) {
+        let next = base + self.parameters.damping * into[*u] / out_degree[*u] as f64;
+        change += (next - *r).abs();
+        *r = next;
+      }
+      if change < self.parameters.tolerance {
+        break;
+      }
+    }
+
+    let (best, _) =
+      rank.iter().enumerate().fold(
+        (0, f64::MIN),
+        |best, (i, r)| if *r > best.1 { (i, *r) } else { best },
+      );
+    let (u, v) = arcs[best];
+    (vertices[u], vertices[v])
+  }
+}
+
+impl<'a> FeedbackArcSet for PageRankHeuristic<'a> {
+  fn feedback_arc_set(&self) -> HashSet<Edge> {
+    let mut graph = self.graph.clone();
+    let mut fas = HashSet::new();
+    for v in self.graph.vertices() {
+      if self.graph.has_edge(v, v) {
+        graph.remove_edge((v, v));
+        fas.insert((v, v));
+      }
+    }
+
+    let mut components = cyclic_components(&graph);
+    while let Some(mut component) = components.pop() {
+      let e = self.highest_ranked_arc(&component);
+      component.remove_edge(e);
+      fas.insert(e);
+      components.extend(cyclic_components(&component));
+    }
+    fas
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::fas::feedback_arc_set::tests::fas_tests;
+  use crate::fas::greedy::GreedyHeuristic;
+  use crate::tools::graphs::graph_from_file;
+
+  fas_tests!(PageRankHeuristic, [h_001, h_025]);
+
+  #[test]
+  fn removes_arc_shared_by_most_cycles() {
+    // The cycles 0 → 1 → 2 → 0 and 0 → 1 → 3 → 0 share the arc 0 → 1
+    let graph = HashTable::from_edges(&[(0, 1), (1, 2), (2, 0), (1, 3), (3, 0)]);
+
+    assert_eq!(
+      PageRankHeuristic::new(&graph).feedback_arc_set(),
+      HashSet::from([(0, 1)])
+    );
+  }
+
+  #[test]
+  fn keeps_self_loops() {
+    let graph = HashTable::from_edges(&[(0, 0), (0, 1), (1, 0)]);
+    let fas = PageRankHeuristic::new(&graph).feedback_arc_set();
+
+    assert_eq!(fas.len(), 2);
+    assert!(fas.contains(&(0, 0)));
+  }
+
+  #[test]
+  fn beats_greedy_on_sparse_graph() {
+    let graph = graph_from_file("h_001");
+
+    assert!(
+      PageRankHeuristic::new(&graph).feedback_arc_set().len()
+        < GreedyHeuristic::new(&graph).feedback_arc_set().len()
+    );
+  }
+}
@@ -0,0 +1,298 @@
+use crate::fas::feedback_arc_set::FeedbackArcSet;
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use crate::ordering::topological_sort::TopologicalSort;
+use crate::scc::strongly_connected_components::cyclic_components;
+use std::collections::{HashMap, HashSet};
+
+/*
+Applies any feedback arc set algorithm to the strongly connected components separately:
+
+decompose(G, depth):
+    F := ∅
+    for every strongly connected component C of G with a cycle      {Tarjan}
+        F := F ∪ self-loops of C                                    {part of every FAS}
+        if C has more than one vertex then
+            F := F ∪ solve(C - self-loops, depth)
+    return F
+
+solve(C, depth):
+    F := fas(C)
+    if recursive and depth < max_depth then
+        H := {(u, v) ∈ F | v is not reachable from u in C - (u, v)}  {each arc alone splits C}
+        if H = ∅ then
+            H := the longest half of F in a topological order of C - F
+        if C - H has no, several or a smaller cyclic component then
+            F' := H ∪ decompose(C - H, depth + 1)
+            if |F'| < |F| then F := F'
+    return F
+
+An arc between two components is on no cycle, so the algorithm never has to consider it.
+Removing H can split C into components that are solved independently; the result is never larger than fas(C).
+The arcs of H are those the algorithm itself chose: arcs that break the strong connectivity of C
+on their own, otherwise the arcs that jump back furthest in its ordering, since the remaining short
+arcs only close cycles within intervals of the ordering. Every level runs the algorithm on parts of
+C once more, so the depth is bounded by max_depth.
+ */
+pub struct SccDecomposition<'a, F: Fn(&HashTable) -> HashSet<Edge>> {
+  pub graph: &'a HashTable,
+  /// Computes the FAS of a strongly connected component, given as induced subgraph with the original vertex ids
+  pub algorithm: F,
+  pub recursive: bool,
+  /// Maximum number of nested decompositions of a component in recursive mode
+  pub max_depth: usize,
+}
+
+impl<'a, F: Fn(&HashTable) -> HashSet<Edge>> SccDecomposition<'a, F> {
+  /// e.g. `SccDecomposition::new(&graph, |component| GreedyHeuristic::new(component).feedback_arc_set())`
+  pub fn new(graph: &'a HashTable, algorithm: F) -> Self {
+    Self {
+      graph,
+      algorithm,
+      recursive: true,
+      max_depth: 4,
+    }
+  }
+
+  /// Without recursion, the algorithm runs exactly once per strongly connected component
+  pub fn with_recursion(mut self, recursive: bool) -> Self {
+    self.recursive = recursive;
+    self
+  }
+
+  pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+    self.max_depth = max_depth;
+    self
+  }
+
+  fn decompose(&self, graph: &HashTable, depth: usize, fas: &mut HashSet<Edge>) {
+    for mut component in cyclic_components(graph) {
+      for v in component.vertices() {
+        if component.has_edge(v, v) {
+          component.remove_edge((v, v));
+          fas.insert((v, v));
+        }
+      }
+      if component.order() > 1 {
+        fas.extend(self.solve(&component, depth));
+      }
+    }
+  }
+
+  fn solve(&self, component: &HashTable, depth: usize) -> HashSet<Edge> {
+    let fas = (self.algorithm)(component);
+    if !self.recursive || depth >= self.max_depth {
+      return fas;
+    }
+
+    let mut arcs = splitting_arcs(component, &fas);
+    if arcs.is_empty() {
+      arcs = longest_arcs(component, &fas);
+    }
+    let mut rest = component.clone();
+    arcs.iter().for_each(|e| rest.remove_edge(*e));
+    let parts = cyclic_components(&rest);
+    // Without cyclic parts H alone is a feedback arc set
+    let splits = match parts.as_slice() {
+      [] => true,
+      [part] => part.order() < component.order(),
+      parts => parts.len() > 1,
+    };
+    if !splits || arcs.is_empty() {
+      return fas;
+    }
+
+    let mut peeled = arcs.into_iter().collect::<HashSet<_>>();
+    self.decompose(&rest, depth + 1, &mut peeled);
+    if peeled.len() < fas.len() {
+      peeled
+    } else {
+      fas
+    }
+  }
+}
+
+/// The arcs of the FAS after whose removal the strongly connected component is no longer strongly connected
+fn splitting_arcs(component: &HashTable, fas: &HashSet<Edge>) -> Vec<Edge> {
+  let mut arcs = fas
+    .iter()
+    .filter(|(u, v)| !is_reachable_without(component, *u, *v))
+    .copied()
+    .collect::<Vec<_>>();
+  arcs.sort_unstable();
+  arcs
+}
+
+/// Whether v is reachable from u without the arc u → v
+fn is_reachable_without(graph: &HashTable, u: VertexId, v: VertexId) -> bool {
+  let mut visited = HashSet::from([u]);
+  let mut stack = vec![u];
+  while let Some(w) = stack.pop() {
+    for &x in graph.neighborhood(&w) {
+      if (w, x) == (u, v) {
+        continue;
+      }
+      if x == v {
+        return true;
+      }
+      if visited.insert(x) {
+        stack.push(x);
+      }
+    }
+  }
+  false
+}
+
+/// The half of the FAS that jumps back furthest in a topological order of the component without the FAS
+fn longest_arcs(component: &HashTable, fas: &HashSet<Edge>) -> Vec<Edge> {
+  let mut rest = component.clone();
+  fas.iter().for_each(|e| rest.remove_edge(*e));
+  let Some(ordering) = TopologicalSort::new(&rest).sort() else {
+    return vec![];
+  };
+  let position = ordering
+    .iter()
+    .enumerate()
+    .map(|(i, v)| (*v, i as i64))
+    .collect::<HashMap<_, _>>();
+
+  let mut arcs = fas.iter().copied().collect::<Vec<_>>();
+  arcs.sort_unstable_by_key(|(u, v)| (position[v] - position[u], *u, *v));
+  arcs.truncate(arcs.len() / 2);
+  arcs
+}
+
+impl<'a, F: Fn(&HashTable) -> HashSet<Edge>> FeedbackArcSet for SccDecomposition<'a, F> {
+  fn feedback_arc_set(&self) -> HashSet<Edge> {
+    let mut fas = HashSet::new();
+    self.decompose(self.graph, 0, &mut fas);
+    fas
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::fas::feedback_arc_set::FeedbackArcSet;
+  use crate::fas::greedy::GreedyHeuristic;
+  use crate::fas::scc_decomposition::SccDecomposition;
+  use crate::graph::hash_table::{Edge, HashTable};
+  use crate::ordering::topological_sort::leftward_edges;
+  use crate::tools::graphs::{graph_from_file, graph_with_multiple_cliques};
+  use std::cell::RefCell;
+  use std::collections::HashSet;
+
+  fn assert_is_feedback_arc_set(graph: &HashTable, fas: &HashSet<Edge>) {
+    let mut acyclic_graph = graph.clone();
+    fas.iter().for_each(|e| acyclic_graph.remove_edge(*e));
+    assert!(!acyclic_graph.is_cyclic());
+  }
+
+  fn greedy(graph: &HashTable) -> HashSet<Edge> {
+    GreedyHeuristic::new(graph).feedback_arc_set()
+  }
+
+  #[test]
+  fn recursion_finds_smaller_fas() {
+    let graph = graph_from_file("h_001");
+    let once = SccDecomposition::new(&graph, greedy)
+      .with_recursion(false)
+      .feedback_arc_set();
+    let recursive = SccDecomposition::new(&graph, greedy).feedback_arc_set();
+
+    assert_is_feedback_arc_set(&graph, &once);
+    assert_is_feedback_arc_set(&graph, &recursive);
+    assert!(
+      recursive.len() < once.len(),
+      "recursive = {}, once = {}",
+      recursive.len(),
+      once.len()
+    );
+  }
+
+  #[test]
+  fn peels_arcs_that_split_the_component() {
+    // Two triangles, 5 → 0 is the only arc back from the second to the first
+    let graph = HashTable::from_edges(&[
+      (0, 1),
+      (1, 2),
+      (2, 0),
+      (2, 3),
+      (3, 4),
+      (4, 5),
+      (5, 3),
+      (5, 0),
+    ]);
+    let orders = RefCell::new(vec![]);
+    let fas = SccDecomposition::new(&graph, |component| {
+      orders.borrow_mut().push(component.order());
+      leftward_edges(component, component.vertices())
+    })
+    .feedback_arc_set();
+
+    assert_is_feedback_arc_set(&graph, &fas);
+    assert_eq!(orders.into_inner(), vec![6, 3, 3]);
+  }
+
+  #[test]
+  fn keeps_peeled_arcs_if_they_break_all_cycles() {
+    // Every pair of the three vertices forms a 2-cycle, the algorithm returns all six arcs
+    let graph = HashTable::from_edges(&[(0, 1), (1, 0), (0, 2), (2, 0), (1, 2), (2, 1)]);
+    let fas = SccDecomposition::new(&graph, |component| {
+      component.all_edges().into_iter().collect()
+    })
+    .feedback_arc_set();
+
+    assert_is_feedback_arc_set(&graph, &fas);
+    assert_eq!(fas.len(), 3);
+  }
+
+  #[test]
+  fn stops_at_max_depth() {
+    let graph = graph_from_file("h_001");
+    let calls = RefCell::new(0);
+    let count_calls = |max_depth| {
+      *calls.borrow_mut() = 0;
+      SccDecomposition::new(&graph, |component| {
+        *calls.borrow_mut() += 1;
+        greedy(component)
+      })
+      .with_max_depth(max_depth)
+      .feedback_arc_set();
+      *calls.borrow()
+    };
+
+    assert_eq!(count_calls(0), 1);
+    assert!(count_calls(1) < count_calls(4));
+  }
+
+  #[test]
+  fn runs_algorithm_only_on_cyclic_components() {
+    let graph = graph_with_multiple_cliques();
+    let orders = RefCell::new(vec![]);
+    let fas = SccDecomposition::new(&graph, |component| {
+      orders.borrow_mut().push(component.order());
+      greedy(component)
+    })
+    .with_recursion(false)
+    .feedback_arc_set();
+
+    assert_is_feedback_arc_set(&graph, &fas);
+    assert_eq!(orders.into_inner(), vec![graph.order() - 3]);
+  }
+
+  #[test]
+  fn self_loops_are_removed_first() {
+    let graph = HashTable::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 2), (2, 3), (4, 4)]);
+    let orders = RefCell::new(vec![]);
+    let fas = SccDecomposition::new(&graph, |component| {
+      assert!(!component.has_edge(2, 2));
+      orders.borrow_mut().push(component.order());
+      greedy(component)
+    })
+    .feedback_arc_set();
+
+    assert_is_feedback_arc_set(&graph, &fas);
+    assert!(fas.contains(&(2, 2)) && fas.contains(&(4, 4)));
+    assert_eq!(fas.len(), 3);
+    assert_eq!(orders.into_inner(), vec![3]);
+  }
+}
@@ -1,5 +1,7 @@
 use crate::fas::feedback_arc_set::FeedbackArcSet;
 use crate::graph::hash_table::{Direction, Edge, HashTable};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use std::collections::HashSet;
 
 /*
@@ -19,11 +21,17 @@ return F.
  */
 pub struct SimpleHeuristic<'a> {
   pub graph: &'a HashTable,
+  /// Seed for selecting the vertices, the same seed always gives the same feedback arc set
+  pub seed: u64,
 }
 
 impl<'a> SimpleHeuristic<'a> {
-  fn new(graph: &'a HashTable) -> Self {
-    Self { graph }
+  pub fn new(graph: &'a HashTable) -> Self {
+    Self::with_seed(graph, 0)
+  }
+
+  pub fn with_seed(graph: &'a HashTable, seed: u64) -> Self {
+    Self { graph, seed }
   }
 }
 
@@ -31,9 +39,10 @@ impl FeedbackArcSet for SimpleHeuristic<'_> {
   fn feedback_arc_set(&self) -> HashSet<Edge> {
     let mut graph = self.graph.clone();
     let mut fas = HashSet::new();
+    let mut rng = StdRng::seed_from_u64(self.seed);
 
     while graph.order() > 0 {
-      let v = graph.random_vertex();
+      let v = graph.random_vertex(&mut rng);
       let edges_in = graph.edges(v, Direction::Inbound);
       let edges_out = graph.edges(v, Direction::Outbound);
 
@@ -63,4 +72,13 @@ mod tests {
   use crate::fas::feedback_arc_set::tests::fas_tests;
 
   fas_tests!(SimpleHeuristic, [h_001, h_025]);
+
+  #[test]
+  fn same_seed_gives_same_fas() {
+    let graph = crate::tools::graphs::graph_from_file("h_001");
+    let fas = |seed| SimpleHeuristic::with_seed(&graph, seed).feedback_arc_set();
+
+    assert_eq!(fas(7), fas(7));
+    assert_ne!(fas(7), fas(8));
+  }
 }
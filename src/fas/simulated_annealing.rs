@@ -0,0 +1,231 @@
+/*
+Simulated annealing over vertex orderings:
+
+s := initial ordering (by default the one of GreedyHeuristic); T := initial_temperature
+while T > final_temperature
+    repeat moves_per_vertex * |V| times, but stop once the time limit is reached {checked every 100 moves}
+        choose a vertex v and one of its neighbors x at random
+        with probability 1/2 move v to the position of x, otherwise swap v and x
+        Δ := change of the weight of the leftward arcs             {O(deg), see Arrangement}
+        accept the move if Δ ≤ 0 or with probability e^(-Δ / T)
+    T := cooling * T
+return the leftward arcs of the best ordering seen
+
+Moves to non-neighbors are never proposed: they only change arcs of v to vertices it passes, so a
+move next to a neighbor is at least as good.
+ */
+
+use crate::fas::feedback_arc_set::FeedbackArcSet;
+use crate::fas::greedy::GreedyHeuristic;
+use crate::fas::sort_fas::indices;
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use crate::ordering::arrangement::{weighted_digraph, Arrangement};
+use crate::ordering::topological_sort::leftward_edges;
+use crate::ordering::vertex_ordering::VertexOrdering;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{BTreeMap, HashSet};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parameters {
+  /// In units of arc weight, a move that adds one leftward arc is accepted with probability 1/e
+  pub initial_temperature: f64,
+  pub final_temperature: f64,
+  /// The temperature is multiplied by this factor after every round of moves
+  pub cooling: f64,
+  /// Moves per temperature in multiples of |V|
+  pub moves_per_vertex: usize,
+  /// Stops early, the ordering then depends on the speed of the machine
+  pub time_limit: Option<Duration>,
+  /// The same seed always gives the same feedback arc set (without time limit)
+  pub seed: u64,
+}
+
+impl Default for Parameters {
+  fn default() -> Self {
+    Self {
+      initial_temperature: 1.0,
+      final_temperature: 0.02,
+      cooling: 0.9,
+      moves_per_vertex: 20,
+      time_limit: None,
+      seed: 0,
+    }
+  }
+}
+
+impl Parameters {
+  /// The default parameters with another seed
+  pub fn with_seed(seed: u64) -> Self {
+    Self {
+      seed,
+      ..Self::default()
+    }
+  }
+}
+
+pub struct SimulatedAnnealing<'a> {
+  pub graph: &'a HashTable,
+  pub parameters: Parameters,
+  /// Starting point of the search, e.g. the ordering of another heuristic, without one the search
+  /// starts with the ordering of GreedyHeuristic
+  pub initial_ordering: Option<Vec<VertexId>>,
+  /// Weight of the arcs, arcs without a weight weigh 1
+  pub weights: BTreeMap<Edge, u64>,
+}
+
+impl<'a> SimulatedAnnealing<'a> {
+  /// Starts with the ordering of GreedyHeuristic
+  pub fn new(graph: &'a HashTable) -> Self {
+    Self::with_parameters(graph, Parameters::default())
+  }
+
+  pub fn with_seed(graph: &'a HashTable, seed: u64) -> Self {
+    Self::with_parameters(graph, Parameters::with_seed(seed))
+  }
+
+  pub fn with_parameters(graph: &'a HashTable, parameters: Parameters) -> Self {
+    assert!(parameters.cooling > 0.0 && parameters.cooling < 1.0);
+    Self {
+      graph,
+      parameters,
+      initial_ordering: None,
+      weights: BTreeMap::new(),
+    }
+  }
+
+  pub fn with_ordering(mut self, initial_ordering: Vec<VertexId>) -> Self {
+    self.initial_ordering = Some(initial_ordering);
+    self
+  }
+
+  pub fn with_weights(mut self, weights: BTreeMap<Edge, u64>) -> Self {
+    self.weights = weights;
+    self
+  }
+}
+
+impl<'a> VertexOrdering for SimulatedAnnealing<'a> {
+  fn vertex_ordering(&self) -> Vec<VertexId> {
+    let start = Instant::now();
+    let initial_ordering = match &self.initial_ordering {
+      Some(ordering) => ordering.clone(),
+      None => GreedyHeuristic::new(self.graph).vertex_ordering(),
+    };
+    let (digraph, vertices) = weighted_digraph(self.graph, &self.weights);
+    let mut arrangement = Arrangement::new(&digraph, indices(&vertices, &initial_ordering));
+    let mut rng = StdRng::seed_from_u64(self.parameters.seed);
+    let has_neighbors = (0..digraph.order())
+      .filter(|v| !digraph.successors[*v].is_empty() || !digraph.predecessors[*v].is_empty())
+      .collect::<Vec<_>>();
+
+    let mut temperature = self.parameters.initial_temperature;
+    'rounds: while temperature > self.parameters.final_temperature && !has_neighbors.is_empty() {
+      for m in 0..self.parameters.moves_per_vertex * digraph.order() {
+        if m % 100 == 0
+          && self
+            .parameters
+            .time_limit
+            .is_some_and(|limit| start.elapsed() >= limit)
+        {
+          break 'rounds;
+        }
+
+        let v = has_neighbors[rng.gen_range(0..has_neighbors.len())];
+        let (successors, predecessors) = (&digraph.successors[v], &digraph.predecessors[v]);
+        let k = rng.gen_range(0..successors.len() + predecessors.len());
+        let (x, _) = successors
+          .get(k)
+          .unwrap_or_else(|| &predecessors[k - successors.len()]);
+        let (from, to) = (arrangement.position[v], arrangement.position[*x]);
+
+        let insertion = rng.gen_bool(0.5);
+        let delta = if insertion {
+          arrangement.insertion_delta(v, to)
+        } else {
+          arrangement.swap_delta(from.min(to), from.max(to))
+        };
+        if delta > 0 && rng.gen::<f64>() >= (-delta as f64 / temperature).exp() {
+          continue;
+        }
+        if insertion {
+          arrangement.insert(v, to, delta);
+        } else {
+          arrangement.swap(from.min(to), from.max(to), delta);
+        }
+      }
+      temperature *= self.parameters.cooling;
+    }
+
+    arrangement
+      .into_best()
+      .into_iter()
+      .map(|i| vertices[i])
+      .collect()
+  }
+}
+
+impl<'a> FeedbackArcSet for SimulatedAnnealing<'a> {
+  fn feedback_arc_set(&self) -> HashSet<Edge> {
+    leftward_edges(self.graph, self.vertex_ordering())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::fas::feedback_arc_set::tests::fas_tests;
+  use crate::tools::graphs::graph_from_file;
+
+  fas_tests!(SimulatedAnnealing, [h_001, h_025]);
+
+  #[test]
+  fn improves_greedy() {
+    let graph = graph_from_file("h_001");
+
+    assert!(
+      SimulatedAnnealing::new(&graph).feedback_arc_set().len()
+        < GreedyHeuristic::new(&graph).feedback_arc_set().len()
+    );
+  }
+
+  #[test]
+  fn prefers_cutting_light_arcs() {
+    // The cycle 0 → 1 → 2 → 0 is broken at its lightest arc
+    let graph = HashTable::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    let weights = BTreeMap::from([((0, 1), 5), ((1, 2), 1), ((2, 0), 5)]);
+    let annealing = SimulatedAnnealing::new(&graph).with_weights(weights);
+
+    assert_eq!(annealing.feedback_arc_set(), HashSet::from([(1, 2)]));
+  }
+
+  #[test]
+  fn stops_at_time_limit() {
+    let graph = graph_from_file("h_025");
+    let parameters = Parameters {
+      final_temperature: 0.0,
+      cooling: 0.999,
+      time_limit: Some(Duration::from_millis(100)),
+      ..Parameters::default()
+    };
+    let start = Instant::now();
+    SimulatedAnnealing::with_parameters(&graph, parameters).feedback_arc_set();
+
+    assert!(start.elapsed() < Duration::from_secs(5));
+  }
+
+  #[test]
+  fn stops_within_a_round() {
+    let graph = graph_from_file("h_001");
+    let parameters = Parameters {
+      moves_per_vertex: 1_000_000,
+      time_limit: Some(Duration::from_millis(100)),
+      ..Parameters::default()
+    };
+    let start = Instant::now();
+    SimulatedAnnealing::with_parameters(&graph, parameters).feedback_arc_set();
+
+    assert!(start.elapsed() < Duration::from_secs(2));
+  }
+}
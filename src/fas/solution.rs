@@ -0,0 +1,241 @@
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use crate::graph::reorientation::Reorientation;
+use crate::ordering::topological_sort::TopologicalSort;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// A feedback arc set together with a topological order of the remaining DAG as certificate of
+/// its acyclicity and the information how it was computed
+#[derive(Clone, Debug, PartialEq)]
+pub struct FasSolution {
+  pub arcs: HashSet<Edge>,
+  /// Topological order of G - arcs, every arc not in the FAS points forward
+  pub ordering: Vec<VertexId>,
+  pub algorithm: String,
+  /// The parameters of the algorithm, e.g. the Debug output of its Parameters struct
+  pub parameters: String,
+  pub seed: Option<u64>,
+  pub wall_time: Duration,
+  /// Number of arcs of the graph the FAS was computed for
+  pub original_arc_count: usize,
+  /// No FAS of the graph is smaller than this, if known
+  pub lower_bound: Option<usize>,
+}
+
+impl FasSolution {
+  /// Computes the certificate, fails if the arcs are no feedback arc set of the graph
+  pub fn new(graph: &HashTable, arcs: HashSet<Edge>) -> Result<Self, String> {
+    let ordering = TopologicalSort::new(&remove(graph, &arcs))
+      .sort()
+      .ok_or("Not a feedback arc set, remaining graph is cyclic")?;
+
+    Ok(Self {
+      arcs,
+      ordering,
+      algorithm: String::new(),
+      parameters: String::new(),
+      seed: None,
+      wall_time: Duration::ZERO,
+      original_arc_count: graph.edge_count(),
+      lower_bound: None,
+    })
+  }
+
+  pub fn with_algorithm(mut self, algorithm: &str, parameters: &str) -> Self {
+    self.algorithm = algorithm.to_string();
+    self.parameters = parameters.to_string();
+    self
+  }
+
+  pub fn with_seed(mut self, seed: u64) -> Self {
+    self.seed = Some(seed);
+    self
+  }
+
+  pub fn with_wall_time(mut self, wall_time: Duration) -> Self {
+    self.wall_time = wall_time;
+    self
+  }
+
+  pub fn with_lower_bound(mut self, lower_bound: usize) -> Self {
+    self.lower_bound = Some(lower_bound);
+    self
+  }
+
+  pub fn size(&self) -> usize {
+    self.arcs.len()
+  }
+
+  /// size / lower bound, 1.0 proves optimality. None without a lower bound
+  pub fn ratio_to_lower_bound(&self) -> Option<f64> {
+    self
+      .lower_bound
+      .map(|lower_bound| ratio(self.size(), lower_bound))
+  }
+
+  /// Checks the solution against the graph without trusting any of its fields
+  pub fn verify(&self, graph: &HashTable) -> Result<(), String> {
+    if self.original_arc_count != graph.edge_count() {
+      return Err(format!(
+        "Computed for {} arcs, but the graph has {}",
+        self.original_arc_count,
+        graph.edge_count()
+      ));
+    }
+    if let Some(e) = self.arcs.iter().find(|(u, v)| !graph.has_edge(*u, *v)) {
+      return Err(format!("{e:?} is not an arc of the graph"));
+    }
+    if self
+      .lower_bound
+      .is_some_and(|lower_bound| self.size() < lower_bound)
+    {
+      return Err("The feedback arc set is smaller than its lower bound".to_string());
+    }
+
+    let position = self
+      .ordering
+      .iter()
+      .enumerate()
+      .map(|(p, v)| (*v, p))
+      .collect::<HashMap<_, _>>();
+    if position.len() != self.ordering.len() || self.ordering.len() != graph.order() {
+      return Err("The ordering is no permutation of the vertices".to_string());
+    }
+    for (u, v) in graph.all_edges() {
+      let (Some(p_u), Some(p_v)) = (position.get(&u), position.get(&v)) else {
+        return Err("The ordering is no permutation of the vertices".to_string());
+      };
+      if p_u >= p_v && !self.arcs.contains(&(u, v)) {
+        return Err(format!(
+          "{:?} points backwards, but is not part of the FAS",
+          (u, v)
+        ));
+      }
+    }
+    Ok(())
+  }
+
+  /// The DAG without the arcs of the FAS
+  pub fn remove_from(&self, graph: &HashTable) -> HashTable {
+    remove(graph, &self.arcs)
+  }
+
+  /// The DAG with the arcs of the FAS reversed, fails if the FAS is not minimal (see Reorientation)
+  pub fn reverse_in(&self, graph: &HashTable) -> Result<Reorientation, String> {
+    Reorientation::new(graph, &self.arcs)
+  }
+}
+
+/// size / lower bound, defined as 1.0 for an acyclic graph whose lower bound and FAS are empty
+pub fn ratio(size: usize, lower_bound: usize) -> f64 {
+  if lower_bound == 0 {
+    if size == 0 {
+      1.0
+    } else {
+      f64::INFINITY
+    }
+  } else {
+    size as f64 / lower_bound as f64
+  }
+}
+
+fn remove(graph: &HashTable, arcs: &HashSet<Edge>) -> HashTable {
+  let mut dag = graph.clone();
+  arcs.iter().for_each(|e| dag.remove_edge(*e));
+  dag
+}
+
+impl Display for FasSolution {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    writeln!(f, "algorithm:   {} {}", self.algorithm, self.parameters)?;
+    if let Some(seed) = self.seed {
+      writeln!(f, "seed:        {seed}")?;
+    }
+    writeln!(
+      f,
+      "fas size:    {} of {} arcs",
+      self.size(),
+      self.original_arc_count
+    )?;
+    if let Some(lower_bound) = self.lower_bound {
+      writeln!(
+        f,
+        "lower bound: {lower_bound} (size / lower bound: {:.3})",
+        ratio(self.size(), lower_bound)
+      )?;
+    }
+    write!(f, "wall time:   {:.3} s", self.wall_time.as_secs_f64())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::fas::solution::FasSolution;
+  use crate::graph::hash_table::HashTable;
+  use crate::tools::graphs::graph_with_simple_clique;
+  use std::collections::HashSet;
+
+  #[test]
+  fn certificate_is_topological_order() {
+    let clique = graph_with_simple_clique();
+    let solution = FasSolution::new(&clique, HashSet::from([(2, 0)])).unwrap();
+
+    assert_eq!(solution.ordering, vec![0, 1, 2]);
+    assert_eq!(solution.original_arc_count, 3);
+    assert_eq!(solution.verify(&clique), Ok(()));
+  }
+
+  #[test]
+  fn rejects_cyclic_remainder() {
+    let clique = graph_with_simple_clique();
+    assert!(FasSolution::new(&clique, HashSet::new()).is_err());
+  }
+
+  #[test]
+  fn verify_detects_tampering() {
+    let clique = graph_with_simple_clique();
+    let solution = FasSolution::new(&clique, HashSet::from([(2, 0)])).unwrap();
+
+    let mut wrong_arc = solution.clone();
+    wrong_arc.arcs.insert((0, 2));
+    assert!(wrong_arc.verify(&clique).is_err());
+
+    let mut wrong_ordering = solution.clone();
+    wrong_ordering.ordering = vec![2, 1, 0];
+    assert!(wrong_ordering.verify(&clique).is_err());
+
+    let wrong_bound = solution.clone().with_lower_bound(2);
+    assert!(wrong_bound.verify(&clique).is_err());
+
+    let other_graph = HashTable::from_edges(&[(0, 1), (2, 0)]);
+    assert!(solution.verify(&other_graph).is_err());
+  }
+
+  #[test]
+  fn shows_ratio_to_lower_bound() {
+    let clique = graph_with_simple_clique();
+    let solution = FasSolution::new(&clique, HashSet::from([(2, 0)])).unwrap();
+    assert_eq!(solution.ratio_to_lower_bound(), None);
+
+    let solution = solution.with_lower_bound(1);
+    assert_eq!(solution.ratio_to_lower_bound(), Some(1.0));
+    assert!(solution
+      .to_string()
+      .contains("lower bound: 1 (size / lower bound: 1.000)"));
+  }
+
+  #[test]
+  fn removes_and_reverses_arcs() {
+    let clique = graph_with_simple_clique();
+    let solution = FasSolution::new(&clique, HashSet::from([(2, 0)])).unwrap();
+
+    assert_eq!(
+      solution.remove_from(&clique).all_edges(),
+      vec![(0, 1), (1, 2)]
+    );
+    let reversed = solution.reverse_in(&clique).unwrap();
+    assert!(reversed.dag.has_edge(0, 2));
+    assert_eq!(reversed.dag.edge_count(), 3);
+  }
+}
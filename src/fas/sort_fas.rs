@@ -0,0 +1,161 @@
+/*
+SortFAS by Brandenburg and Hanauer (Sorting heuristics for the feedback arc set problem, 2011),
+an insertion sort on the vertices:
+
+s := ∅
+for every vertex v in the initial ordering
+    insert v into s at the position with the fewest leftward arcs
+return s
+
+The leftward arcs of every position follow from one scan over s: in front of all vertices only
+the arcs from predecessors point leftwards, and passing a vertex u changes this by w(v → u) - w(u → v).
+Ties keep v at the end of s, so a pass costs O(|V|^2).
+ */
+
+use crate::bisection::weighted_digraph::WeightedDigraph;
+use crate::fas::feedback_arc_set::FeedbackArcSet;
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use crate::ordering::topological_sort::leftward_edges;
+use crate::ordering::vertex_ordering::VertexOrdering;
+use std::collections::HashSet;
+
+pub struct SortFas<'a> {
+  pub graph: &'a HashTable,
+  /// Order in which the vertices are inserted
+  pub initial_ordering: Vec<VertexId>,
+}
+
+impl<'a> SortFas<'a> {
+  /// Inserts the vertices by increasing id
+  pub fn new(graph: &'a HashTable) -> Self {
+    Self::with_ordering(graph, graph.vertices())
+  }
+
+  pub fn with_ordering(graph: &'a HashTable, initial_ordering: Vec<VertexId>) -> Self {
+    Self {
+      graph,
+      initial_ordering,
+    }
+  }
+}
+
+impl<'a> VertexOrdering for SortFas<'a> {
+  fn vertex_ordering(&self) -> Vec<VertexId> {
+    let (digraph, vertices) = WeightedDigraph::from_graph(self.graph);
+    let mut insertion = Insertion::new(&digraph);
+
+    let mut ordering: Vec<usize> = Vec::with_capacity(vertices.len());
+    for v in indices(&vertices, &self.initial_ordering) {
+      let (position, _) = insertion.best_position(&ordering, v, ordering.len());
+      ordering.insert(position, v);
+    }
+    ordering.into_iter().map(|i| vertices[i]).collect()
+  }
+}
+
+impl<'a> FeedbackArcSet for SortFas<'a> {
+  fn feedback_arc_set(&self) -> HashSet<Edge> {
+    leftward_edges(self.graph, self.vertex_ordering())
+  }
+}
+
+/// The indices of the vertices of an ordering in the sorted vertex list of WeightedDigraph::from_graph
+pub(crate) fn indices(vertices: &[VertexId], ordering: &[VertexId]) -> Vec<usize> {
+  assert_eq!(
+    ordering.len(),
+    vertices.len(),
+    "Ordering needs every vertex once"
+  );
+  ordering
+    .iter()
+    .map(|v| {
+      vertices
+        .binary_search(v)
+        .expect("Unknown vertex in ordering")
+    })
+    .collect()
+}
+
+/// Finds the best position to insert a vertex into a sequence of the other vertices
+pub(crate) struct Insertion<'g> {
+  graph: &'g WeightedDigraph,
+  // Change of the leftward weight when v passes the vertex, only non-zero for neighbors of v
+  change: Vec<i64>,
+}
+
+impl<'g> Insertion<'g> {
+  pub(crate) fn new(graph: &'g WeightedDigraph) -> Self {
+    Self {
+      graph,
+      change: vec![0; graph.order()],
+    }
+  }
+
+  /// Position in others with the least leftward weight of the arcs of v and the difference to the
+  /// weight at position current, which wins ties. others must not contain v.
+  pub(crate) fn best_position(
+    &mut self,
+    others: &[usize],
+    v: usize,
+    current: usize,
+  ) -> (usize, i64) {
+    for (u, weight) in &self.graph.successors[v] {
+      self.change[*u] += weight;
+    }
+    for (u, weight) in &self.graph.predecessors[v] {
+      self.change[*u] -= weight;
+    }
+
+    // The weight at position 0 is the same for all positions, so it is left out
+    let mut weight = 0;
+    let mut weights = Vec::with_capacity(others.len() + 1);
+    weights.push(0);
+    for u in others {
+      weight += self.change[*u];
+      weights.push(weight);
+    }
+
+    for (u, _) in self.graph.successors[v]
+      .iter()
+      .chain(&self.graph.predecessors[v])
+    {
+      self.change[*u] = 0;
+    }
+
+    let best = (0..weights.len())
+      .min_by_key(|p| (weights[*p], *p != current))
+      .unwrap();
+    (best, weights[best] - weights[current])
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::fas::feedback_arc_set::tests::fas_tests;
+  use crate::fas::greedy::GreedyHeuristic;
+  use crate::tools::graphs::graph_from_file;
+
+  fas_tests!(SortFas, [h_001, h_025]);
+
+  #[test]
+  fn inserts_at_position_with_fewest_leftward_arcs() {
+    // 0 → 2, 1 → 2 and 2 → 3 → 0: the cycle leaves one leftward arc, ties keep 0 at the end
+    let graph = HashTable::from_edges(&[(0, 2), (1, 2), (2, 3), (3, 0)]);
+
+    assert_eq!(SortFas::new(&graph).vertex_ordering(), vec![0, 1, 2, 3]);
+    assert_eq!(
+      SortFas::with_ordering(&graph, vec![3, 2, 1, 0]).vertex_ordering(),
+      vec![1, 2, 3, 0]
+    );
+  }
+
+  #[test]
+  fn sorting_greedy_ordering_does_not_hurt_much() {
+    let graph = graph_from_file("h_025");
+    let greedy = GreedyHeuristic::new(&graph);
+    let sorted = SortFas::with_ordering(&graph, greedy.vertex_ordering());
+
+    assert!(sorted.feedback_arc_set().len() <= greedy.feedback_arc_set().len() * 11 / 10);
+  }
+}
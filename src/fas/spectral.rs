@@ -0,0 +1,323 @@
+/*
+Spectral ordering: the vertices are sorted by an eigenvector of a Laplacian of G, which takes all
+arcs into account at once instead of single vertices (as the greedy heuristics do).
+
+Magnetic Laplacian L = D - H with H(u, v) = w(u, v) e^(i 2π q (A(u, v) - A(v, u))), where w(u, v) is
+the number of arcs between u and v and D(u, u) = Σ_v w(u, v). Along an arc the eigenvector of the
+smallest eigenvalue turns by an angle of about 2π q, so the vertices are sorted by the phase of their
+entry. The phases lie on a circle, which is cut at the largest gap.
+
+Symmetrized Laplacian L = D - (A + Aᵀ): the vertices are sorted by the Fiedler vector, i.e. the
+eigenvector of the second smallest eigenvalue. It ignores the arc directions.
+
+Both eigenvectors are computed by power iteration on c I - L with c = 2 max degree ≥ λmax, whose
+largest eigenvalue belongs to the smallest of L. For the Fiedler vector the constant vector (the
+eigenvector of 0) is projected out in every iteration. An eigenvector does not tell which end is
+first, so the direction with fewer leftward arcs is returned.
+ */
+
+use crate::fas::feedback_arc_set::FeedbackArcSet;
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use crate::ordering::topological_sort::leftward_edges;
+use crate::ordering::vertex_ordering::VertexOrdering;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{BTreeMap, HashSet};
+use std::f64::consts::PI;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Laplacian {
+  /// Magnetic Laplacian with charge q, 0 < q ≤ 1/4
+  Magnetic {
+    q: f64,
+  },
+  Symmetrized,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parameters {
+  pub laplacian: Laplacian,
+  /// The power iteration stops as soon as the eigenvector changes by less than this (L2 norm)
+  pub tolerance: f64,
+  pub max_iterations: usize,
+  /// Seed of the random start vector
+  pub seed: u64,
+}
+
+impl Default for Parameters {
+  fn default() -> Self {
+    Self {
+      laplacian: Laplacian::Magnetic { q: 0.02 },
+      tolerance: 1e-9,
+      max_iterations: 2000,
+      seed: 0,
+    }
+  }
+}
+
+impl Parameters {
+  /// The default parameters with another seed
+  pub fn with_seed(seed: u64) -> Self {
+    Self {
+      seed,
+      ..Self::default()
+    }
+  }
+}
+
+pub struct SpectralHeuristic<'a> {
+  pub graph: &'a HashTable,
+  pub parameters: Parameters,
+}
+
+impl<'a> SpectralHeuristic<'a> {
+  pub fn new(graph: &'a HashTable) -> Self {
+    Self::with_parameters(graph, Parameters::default())
+  }
+
+  pub fn with_seed(graph: &'a HashTable, seed: u64) -> Self {
+    Self::with_parameters(graph, Parameters::with_seed(seed))
+  }
+
+  pub fn with_parameters(graph: &'a HashTable, parameters: Parameters) -> Self {
+    Self { graph, parameters }
+  }
+
+  /// The sort key of every vertex index, the phase or the entry of the Fiedler vector, and whether
+  /// the power iteration converged
+  fn embedding(&self, vertices: &[VertexId]) -> (Vec<f64>, bool) {
+    let q = match self.parameters.laplacian {
+      Laplacian::Magnetic { q } => q,
+      Laplacian::Symmetrized => 0.0,
+    };
+    let neighbors = hermitian_neighbors(self.graph, vertices, q);
+    let degree = neighbors
+      .iter()
+      .map(|arcs| arcs.iter().map(|(_, w, _)| w).sum::<f64>())
+      .collect::<Vec<_>>();
+    let shift = 2.0 * degree.iter().cloned().fold(0.0, f64::max);
+
+    let mut rng = StdRng::seed_from_u64(self.parameters.seed);
+    let n = vertices.len();
+    let mut x = (0..n)
+      .map(|_| Complex(rng.gen_range(-1.0..1.0), rng.gen_range(-1.0..1.0)))
+      .collect::<Vec<_>>();
+    let symmetrized = self.parameters.laplacian == Laplacian::Symmetrized;
+    if symmetrized {
+      x.iter_mut().for_each(|z| z.1 = 0.0);
+      remove_mean(&mut x);
+    }
+    normalize(&mut x);
+
+    let mut converged = false;
+    for _ in 0..self.parameters.max_iterations {
+      // y = (c I - L) x = (c - D) x + H x
+      let mut y = (0..n)
+        .map(|u| {
+          let mut z = x[u].scale(shift - degree[u]);
+          for (v, weight, phase) in &neighbors[u] {
+            z = z.add(phase.mul(x[*v]).scale(*weight));
+          }
+          z
+        })
+        .collect::<Vec<_>>();
+      if symmetrized {
+        remove_mean(&mut y);
+      }
+      normalize(&mut y);
+
+      let change = x
+        .iter()
+        .zip(&y)
+        .map(|(a, b)| a.add(b.scale(-1.0)).norm_sqr())
+        .sum::<f64>()
+        .sqrt();
+      x = y;
+      if change < self.parameters.tolerance {
+        converged = true;
+        break;
+      }
+    }
+
+    let key = if symmetrized {
+      x.iter().map(|z| z.0).collect()
+    } else {
+      x.iter().map(|z| z.1.atan2(z.0)).collect()
+    };
+    (key, converged)
+  }
+
+  /// Like vertex_ordering, but also tells whether the power iteration converged within max_iterations.
+  /// Otherwise the ordering is based on an approximation of the eigenvector.
+  pub fn vertex_ordering_with_convergence(&self) -> (Vec<VertexId>, bool) {
+    let vertices = self.graph.vertices();
+    if vertices.is_empty() {
+      return (vertices, true);
+    }
+    let (key, converged) = self.embedding(&vertices);
+    let mut order = (0..vertices.len()).collect::<Vec<_>>();
+    order.sort_by(|u, v| key[*u].total_cmp(&key[*v]).then(u.cmp(v)));
+
+    if matches!(self.parameters.laplacian, Laplacian::Magnetic { .. }) {
+      // Phases are angles, so the circle is cut behind the largest gap
+      let gap = |i: usize| {
+        let next = key[order[(i + 1) % order.len()]];
+        (next - key[order[i]]).rem_euclid(2.0 * PI)
+      };
+      let largest = (0..order.len())
+        .max_by(|i, j| gap(*i).total_cmp(&gap(*j)).then(j.cmp(i)))
+        .unwrap();
+      let n = order.len();
+      order.rotate_left((largest + 1) % n);
+    }
+
+    let ordering = order.into_iter().map(|i| vertices[i]).collect::<Vec<_>>();
+    let mut reversed = ordering.clone();
+    reversed.reverse();
+    if leftward_edges(self.graph, reversed.clone()).len()
+      < leftward_edges(self.graph, ordering.clone()).len()
+    {
+      (reversed, converged)
+    } else {
+      (ordering, converged)
+    }
+  }
+}
+
+impl<'a> VertexOrdering for SpectralHeuristic<'a> {
+  fn vertex_ordering(&self) -> Vec<VertexId> {
+    self.vertex_ordering_with_convergence().0
+  }
+}
+
+impl<'a> FeedbackArcSet for SpectralHeuristic<'a> {
+  fn feedback_arc_set(&self) -> HashSet<Edge> {
+    leftward_edges(self.graph, self.vertex_ordering())
+  }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Complex(f64, f64);
+
+impl Complex {
+  fn add(self, other: Complex) -> Complex {
+    Complex(self.0 + other.0, self.1 + other.1)
+  }
+
+  fn mul(self, other: Complex) -> Complex {
+    Complex(
+      self.0 * other.0 - self.1 * other.1,
+      self.0 * other.1 + self.1 * other.0,
+    )
+  }
+
+  fn scale(self, factor: f64) -> Complex {
+    Complex(self.0 * factor, self.1 * factor)
+  }
+
+  fn norm_sqr(self) -> f64 {
+    self.0 * self.0 + self.1 * self.1
+  }
+}
+
+// The non-zero entries (v, w(u, v), e^(i 2π q (A(u, v) - A(v, u)))) of row u of H, self-loops are left out
+fn hermitian_neighbors(
+  graph: &HashTable,
+  vertices: &[VertexId],
+  q: f64,
+) -> Vec<Vec<(usize, f64, Complex)>> {
+  let index = |v: &VertexId| vertices.binary_search(v).unwrap();
+  // (u, v) with u < v → A(u, v) - A(v, u) and the number of arcs between them
+  let mut pairs = BTreeMap::<(usize, usize), (i32, f64)>::new();
+  for (u, v) in graph.all_edges() {
+    let (u, v) = (index(&u), index(&v));
+    if u == v {
+      continue;
+    }
+    let entry = pairs.entry((u.min(v), u.max(v))).or_default();
+    entry.0 += if u < v { 1 } else { -1 };
+    entry.1 += 1.0;
+  }
+
+  let mut neighbors = vec![vec![]; vertices.len()];
+  for ((u, v), (direction, weight)) in pairs {
+    let angle = 2.0 * PI * q * direction as f64;
+    neighbors[u].push((v, weight, Complex(angle.cos(), angle.sin())));
+    neighbors[v].push((u, weight, Complex(angle.cos(), -angle.sin())));
+  }
+  neighbors
+}
+
+fn normalize(x: &mut [Complex]) {
+  let norm = x.iter().map(|z| z.norm_sqr()).sum::<f64>().sqrt();
+  if norm > 0.0 {
+    x.iter_mut().for_each(|z| *z = z.scale(1.0 / norm));
+  }
+}
+
+fn remove_mean(x: &mut [Complex]) {
+  let mean = x.iter().map(|z| z.0).sum::<f64>() / x.len() as f64;
+  x.iter_mut().for_each(|z| z.0 -= mean);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::fas::feedback_arc_set::tests::fas_tests;
+  use crate::fas::greedy::GreedyHeuristic;
+  use crate::tools::graphs::graph_from_file;
+
+  fas_tests!(SpectralHeuristic, [h_001, h_025]);
+
+  #[test]
+  fn orders_path() {
+    let graph = HashTable::from_edges(&[(3, 0), (0, 4), (4, 1), (1, 2)]);
+    let symmetrized = Parameters {
+      laplacian: Laplacian::Symmetrized,
+      ..Parameters::default()
+    };
+
+    assert_eq!(
+      SpectralHeuristic::new(&graph).vertex_ordering(),
+      vec![3, 0, 4, 1, 2]
+    );
+    assert_eq!(
+      SpectralHeuristic::with_parameters(&graph, symmetrized).vertex_ordering(),
+      vec![3, 0, 4, 1, 2]
+    );
+  }
+
+  #[test]
+  fn reports_convergence() {
+    let graph = HashTable::from_edges(&[(3, 0), (0, 4), (4, 1), (1, 2)]);
+    let one_iteration = Parameters {
+      max_iterations: 1,
+      ..Parameters::default()
+    };
+
+    assert!(
+      SpectralHeuristic::new(&graph)
+        .vertex_ordering_with_convergence()
+        .1
+    );
+    assert!(
+      !SpectralHeuristic::with_parameters(&graph, one_iteration)
+        .vertex_ordering_with_convergence()
+        .1
+    );
+  }
+
+  #[test]
+  fn beats_greedy_on_dense_graph() {
+    let graph = graph_from_file("h_025");
+    let (ordering, converged) = SpectralHeuristic::new(&graph).vertex_ordering_with_convergence();
+    let spectral = leftward_edges(&graph, ordering).len();
+    let greedy = GreedyHeuristic::new(&graph).feedback_arc_set().len();
+
+    assert!(converged);
+    assert!(
+      spectral < greedy,
+      "spectral = {spectral}, greedy = {greedy}"
+    );
+  }
+}
@@ -0,0 +1,151 @@
+/*
+Exact feedback arc set by dynamic programming over vertex subsets (as Held and Karp for the TSP):
+
+f(∅) := 0
+for every subset S of V by increasing size
+    f(S) := min over v ∈ S of f(S - v) + w(arcs from v to S - v)   {v is the last vertex of S}
+return the ordering that realizes f(V), its leftward arcs are a minimum FAS
+
+Needs O(2^|V|) memory and O(2^|V| |E|) time, so it is only usable on small graphs or as the base case
+of a recursion (see KwikSort).
+ */
+
+use crate::bisection::weighted_digraph::WeightedDigraph;
+use crate::fas::feedback_arc_set::FeedbackArcSet;
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use crate::ordering::topological_sort::leftward_edges;
+use crate::ordering::vertex_ordering::VertexOrdering;
+use std::collections::HashSet;
+
+/// Largest number of vertices SubsetDp accepts
+pub const MAX_ORDER: usize = 24;
+
+pub struct SubsetDp<'a> {
+  pub graph: &'a HashTable,
+}
+
+impl<'a> SubsetDp<'a> {
+  pub fn new(graph: &'a HashTable) -> Self {
+    assert!(
+      graph.order() <= MAX_ORDER,
+      "The subset DP is limited to {MAX_ORDER} vertices"
+    );
+    Self { graph }
+  }
+}
+
+impl<'a> VertexOrdering for SubsetDp<'a> {
+  fn vertex_ordering(&self) -> Vec<VertexId> {
+    let (digraph, vertices) = WeightedDigraph::from_graph(self.graph);
+    let all = (0..digraph.order()).collect::<Vec<_>>();
+
+    optimal_ordering(&digraph, &all)
+      .into_iter()
+      .map(|i| vertices[i])
+      .collect()
+  }
+}
+
+impl<'a> FeedbackArcSet for SubsetDp<'a> {
+  fn feedback_arc_set(&self) -> HashSet<Edge> {
+    leftward_edges(self.graph, self.vertex_ordering())
+  }
+}
+
+/// Ordering of the given vertices of the graph with the least weight of leftward arcs among them
+pub(crate) fn optimal_ordering(graph: &WeightedDigraph, vertices: &[usize]) -> Vec<usize> {
+  let k = vertices.len();
+  assert!(k <= MAX_ORDER);
+
+  // Arcs between the vertices as (bit of the head, weight) per tail
+  let mut bit = vec![usize::MAX; graph.order()];
+  vertices.iter().enumerate().for_each(|(i, v)| bit[*v] = i);
+  let arcs = vertices
+    .iter()
+    .map(|v| {
+      graph.successors[*v]
+        .iter()
+        .filter(|(u, _)| bit[*u] != usize::MAX)
+        .map(|(u, weight)| (bit[*u], *weight))
+        .collect::<Vec<_>>()
+    })
+    .collect::<Vec<_>>();
+
+  let mut cost = vec![i64::MAX; 1 << k];
+  let mut last = vec![0u8; 1 << k];
+  cost[0] = 0;
+  for subset in 1usize..1 << k {
+    for v in (0..k).filter(|v| subset & (1 << v) != 0) {
+      let rest = subset & !(1 << v);
+      let leftward = arcs[v]
+        .iter()
+        .filter(|(u, _)| rest & (1 << u) != 0)
+        .map(|(_, weight)| weight)
+        .sum::<i64>();
+      if cost[rest] + leftward < cost[subset] {
+        cost[subset] = cost[rest] + leftward;
+        last[subset] = v as u8;
+      }
+    }
+  }
+
+  let mut ordering = Vec::with_capacity(k);
+  let mut subset = (1usize << k) - 1;
+  while subset != 0 {
+    let v = last[subset] as usize;
+    ordering.push(vertices[v]);
+    subset &= !(1 << v);
+  }
+  ordering.reverse();
+  ordering
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::fas::feedback_arc_set::tests::fas_tests;
+  use crate::generator::tournament::random_tournament;
+  use crate::tools::graphs::graph_from_wikipedia_scc;
+  use rand::rngs::StdRng;
+  use rand::SeedableRng;
+
+  fas_tests!(SubsetDp, []);
+
+  // Minimum FAS by trying all subsets of arcs
+  fn brute_force(graph: &HashTable) -> usize {
+    let edges = graph.all_edges();
+    (0usize..1 << edges.len())
+      .filter(|subset| {
+        let mut acyclic_graph = graph.clone();
+        (0..edges.len())
+          .filter(|i| subset & (1 << i) != 0)
+          .for_each(|i| acyclic_graph.remove_edge(edges[i]));
+        !acyclic_graph.is_cyclic()
+      })
+      .map(|subset| subset.count_ones() as usize)
+      .min()
+      .unwrap()
+  }
+
+  #[test]
+  fn finds_minimum_on_wikipedia_scc() {
+    let graph = graph_from_wikipedia_scc();
+
+    assert_eq!(
+      SubsetDp::new(&graph).feedback_arc_set().len(),
+      brute_force(&graph)
+    );
+  }
+
+  #[test]
+  fn finds_minimum_on_small_tournaments() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..5 {
+      let graph = random_tournament(5, &mut rng);
+      assert_eq!(
+        SubsetDp::new(&graph).feedback_arc_set().len(),
+        brute_force(&graph)
+      );
+    }
+  }
+}
@@ -0,0 +1,244 @@
+/*
+Tabu search over vertex orderings:
+
+s := initial ordering (by default the one of GreedyHeuristic)
+repeat max_iterations times or until the time limit is reached
+    C := candidates random vertices and swaps of each with a random successor
+    choose the best move of C                             {insertions at their best position next to a neighbor, O(deg log deg)}
+        that does not move a tabu vertex, unless it leads to a new best ordering (aspiration)
+    apply it, even if it adds leftward arcs
+    the moved vertices are tabu for the next tenure iterations
+return the leftward arcs of the best ordering seen
+ */
+
+use crate::fas::feedback_arc_set::FeedbackArcSet;
+use crate::fas::greedy::GreedyHeuristic;
+use crate::fas::sort_fas::indices;
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use crate::ordering::arrangement::{weighted_digraph, Arrangement};
+use crate::ordering::topological_sort::leftward_edges;
+use crate::ordering::vertex_ordering::VertexOrdering;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::{BTreeMap, HashSet};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parameters {
+  /// Number of iterations a moved vertex may not be moved again
+  pub tenure: usize,
+  /// Number of vertices, whose best moves are compared in each iteration
+  pub candidates: usize,
+  pub max_iterations: usize,
+  /// Stops early, the ordering then depends on the speed of the machine
+  pub time_limit: Option<Duration>,
+  /// The same seed always gives the same feedback arc set (without time limit)
+  pub seed: u64,
+}
+
+impl Default for Parameters {
+  fn default() -> Self {
+    Self {
+      tenure: 20,
+      candidates: 16,
+      max_iterations: 20_000,
+      time_limit: None,
+      seed: 0,
+    }
+  }
+}
+
+impl Parameters {
+  /// The default parameters with another seed
+  pub fn with_seed(seed: u64) -> Self {
+    Self {
+      seed,
+      ..Self::default()
+    }
+  }
+}
+
+pub struct TabuSearch<'a> {
+  pub graph: &'a HashTable,
+  pub parameters: Parameters,
+  /// Starting point of the search, e.g. the ordering of another heuristic, without one the search
+  /// starts with the ordering of GreedyHeuristic
+  pub initial_ordering: Option<Vec<VertexId>>,
+  /// Weight of the arcs, arcs without a weight weigh 1
+  pub weights: BTreeMap<Edge, u64>,
+}
+
+impl<'a> TabuSearch<'a> {
+  /// Starts with the ordering of GreedyHeuristic
+  pub fn new(graph: &'a HashTable) -> Self {
+    Self::with_parameters(graph, Parameters::default())
+  }
+
+  pub fn with_seed(graph: &'a HashTable, seed: u64) -> Self {
+    Self::with_parameters(graph, Parameters::with_seed(seed))
+  }
+
+  pub fn with_parameters(graph: &'a HashTable, parameters: Parameters) -> Self {
+    assert!(parameters.candidates > 0);
+    Self {
+      graph,
+      parameters,
+      initial_ordering: None,
+      weights: BTreeMap::new(),
+    }
+  }
+
+  pub fn with_ordering(mut self, initial_ordering: Vec<VertexId>) -> Self {
+    self.initial_ordering = Some(initial_ordering);
+    self
+  }
+
+  pub fn with_weights(mut self, weights: BTreeMap<Edge, u64>) -> Self {
+    self.weights = weights;
+    self
+  }
+}
+
+#[derive(Clone, Copy)]
+enum Move {
+  Insertion { v: usize, to: usize },
+  Swap { i: usize, j: usize },
+}
+
+impl<'a> VertexOrdering for TabuSearch<'a> {
+  fn vertex_ordering(&self) -> Vec<VertexId> {
+    let start = Instant::now();
+    let initial_ordering = match &self.initial_ordering {
+      Some(ordering) => ordering.clone(),
+      None => GreedyHeuristic::new(self.graph).vertex_ordering(),
+    };
+    let (digraph, vertices) = weighted_digraph(self.graph, &self.weights);
+    let n = digraph.order();
+    let mut arrangement = Arrangement::new(&digraph, indices(&vertices, &initial_ordering));
+    let mut rng = StdRng::seed_from_u64(self.parameters.seed);
+    // Iteration from which on a vertex may be moved again
+    let mut tabu_until = vec![0; n];
+
+    for iteration in 0..self.parameters.max_iterations {
+      if n < 2 {
+        break;
+      }
+      if iteration % 100 == 0
+        && self
+          .parameters
+          .time_limit
+          .is_some_and(|limit| start.elapsed() >= limit)
+      {
+        break;
+      }
+
+      let is_allowed = |vertices: &[usize], delta: i64| {
+        vertices.iter().all(|v| tabu_until[*v] <= iteration)
+          || arrangement.cost + delta < arrangement.best_cost
+      };
+      let mut best: Option<(i64, Move)> = None;
+      for _ in 0..self.parameters.candidates {
+        let v = rng.gen_range(0..n);
+        let (to, delta) = arrangement.best_insertion(v);
+        if to != arrangement.position[v]
+          && is_allowed(&[v], delta)
+          && best.is_none_or(|(d, _)| delta < d)
+        {
+          best = Some((delta, Move::Insertion { v, to }));
+        }
+
+        let neighbors = &digraph.successors[v];
+        if let Some((x, _)) = neighbors.get(rng.gen_range(0..neighbors.len().max(1))) {
+          let (p, q) = (arrangement.position[v], arrangement.position[*x]);
+          let (i, j) = (p.min(q), p.max(q));
+          let delta = arrangement.swap_delta(i, j);
+          if is_allowed(&[v, *x], delta) && best.is_none_or(|(d, _)| delta < d) {
+            best = Some((delta, Move::Swap { i, j }));
+          }
+        }
+      }
+
+      match best {
+        Some((delta, Move::Insertion { v, to })) => {
+          arrangement.insert(v, to, delta);
+          tabu_until[v] = iteration + self.parameters.tenure + 1;
+        }
+        Some((delta, Move::Swap { i, j })) => {
+          let (u, v) = (arrangement.ordering[i], arrangement.ordering[j]);
+          arrangement.swap(i, j, delta);
+          tabu_until[u] = iteration + self.parameters.tenure + 1;
+          tabu_until[v] = iteration + self.parameters.tenure + 1;
+        }
+        None => (),
+      }
+    }
+
+    arrangement
+      .into_best()
+      .into_iter()
+      .map(|i| vertices[i])
+      .collect()
+  }
+}
+
+impl<'a> FeedbackArcSet for TabuSearch<'a> {
+  fn feedback_arc_set(&self) -> HashSet<Edge> {
+    leftward_edges(self.graph, self.vertex_ordering())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::fas::feedback_arc_set::tests::fas_tests;
+  use crate::fas::spectral::SpectralHeuristic;
+  use crate::tools::graphs::graph_from_file;
+
+  fas_tests!(TabuSearch, [h_001, h_025]);
+
+  #[test]
+  fn improves_greedy() {
+    let graph = graph_from_file("h_001");
+
+    assert!(
+      TabuSearch::new(&graph).feedback_arc_set().len()
+        < GreedyHeuristic::new(&graph).feedback_arc_set().len()
+    );
+  }
+
+  #[test]
+  fn improves_any_initial_ordering() {
+    let graph = graph_from_file("h_025");
+    let spectral = SpectralHeuristic::new(&graph);
+    let tabu = TabuSearch::new(&graph).with_ordering(spectral.vertex_ordering());
+
+    assert!(tabu.feedback_arc_set().len() < spectral.feedback_arc_set().len());
+  }
+
+  #[test]
+  fn leaves_local_optimum_of_insertions() {
+    let graph = graph_from_file("h_001");
+    let (digraph, vertices) = weighted_digraph(&graph, &BTreeMap::new());
+    let greedy = GreedyHeuristic::new(&graph).vertex_ordering();
+    let mut arrangement = Arrangement::new(&digraph, indices(&vertices, &greedy));
+    let mut improved = true;
+    while improved {
+      improved = false;
+      for v in 0..digraph.order() {
+        let (to, delta) = arrangement.best_insertion(v);
+        if delta < 0 {
+          arrangement.insert(v, to, delta);
+          improved = true;
+        }
+      }
+    }
+    let local_optimum = arrangement
+      .into_best()
+      .into_iter()
+      .map(|i| vertices[i])
+      .collect::<Vec<_>>();
+
+    let tabu = TabuSearch::new(&graph).with_ordering(local_optimum.clone());
+    assert!(tabu.feedback_arc_set().len() < leftward_edges(&graph, local_optimum).len());
+  }
+}
@@ -0,0 +1,117 @@
+use crate::graph::hash_table::{HashTable, VertexId};
+
+/// Number of vertices d^k of B(d, k), None for words of length 0 or if the vertex ids do not fit
+/// into VertexId
+pub fn de_bruijn_order(d: usize, k: usize) -> Option<usize> {
+  if k == 0 {
+    return None;
+  }
+  let n = d.checked_pow(u32::try_from(k).ok()?)?;
+  // x * d is computed for every vertex x
+  n.checked_mul(d)?;
+  VertexId::try_from(n).ok().map(|_| n)
+}
+
+/// Number of vertices (d + 1) d^(k - 1) of K(d, k), None if the vertex ids do not fit into VertexId
+pub fn kautz_order(d: usize, k: usize) -> Option<usize> {
+  let n = d
+    .checked_pow(u32::try_from(k.checked_sub(1)?).ok()?)?
+    .checked_mul(d + 1)?;
+  VertexId::try_from(n).ok().map(|_| n)
+}
+
+/// De Bruijn digraph B(d, k): the vertices are the words of length k over d symbols, read as numbers
+/// in base d, and every word x1 x2 ... xk points to the d words x2 ... xk y. Contains d self-loops.
+/// Panics if d^k vertices are too many, see de_bruijn_order.
+pub fn de_bruijn(d: usize, k: usize) -> HashTable {
+  assert!(d > 0 && k > 0);
+  let n = de_bruijn_order(d, k).expect("B(d, k) has too many vertices");
+  let vertices = (0..n as VertexId).collect::<Vec<_>>();
+  let mut edges = vec![];
+  for x in 0..n {
+    for y in 0..d {
+      edges.push((x as VertexId, ((x * d) % n + y) as VertexId));
+    }
+  }
+  HashTable::from_vertices_and_edges(&vertices, &edges)
+}
+
+/// Kautz digraph K(d, k): the vertices are the words of length k over d + 1 symbols without two
+/// equal consecutive symbols, and every word x1 x2 ... xk points to the d words x2 ... xk y with
+/// y ≠ xk. Vertices are numbered in lexicographic order of their words.
+/// Panics if (d + 1) d^(k - 1) vertices are too many, see kautz_order.
+pub fn kautz(d: usize, k: usize) -> HashTable {
+  assert!(d > 0 && k > 0);
+  kautz_order(d, k).expect("K(d, k) has too many vertices");
+  let mut words: Vec<Vec<usize>> = (0..=d).map(|x| vec![x]).collect();
+  for _ in 1..k {
+    words = words
+      .into_iter()
+      .flat_map(|word| {
+        let last = word[word.len() - 1];
+        (0..=d).filter(move |y| *y != last).map(move |y| {
+          let mut longer = word.clone();
+          longer.push(y);
+          longer
+        })
+      })
+      .collect();
+  }
+
+  // Words are generated in lexicographic order
+  let id = |word: &[usize]| words.binary_search_by(|w| w[..].cmp(word)).unwrap() as VertexId;
+  let vertices = (0..words.len() as VertexId).collect::<Vec<_>>();
+  let mut edges = vec![];
+  for word in &words {
+    let last = word[k - 1];
+    for y in (0..=d).filter(|y| *y != last) {
+      let mut successor = word[1..].to_vec();
+      successor.push(y);
+      edges.push((id(word), id(&successor)));
+    }
+  }
+  HashTable::from_vertices_and_edges(&vertices, &edges)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::generator::de_bruijn::{de_bruijn, de_bruijn_order, kautz, kautz_order};
+
+  #[test]
+  fn de_bruijn_has_d_successors() {
+    let graph = de_bruijn(2, 3);
+
+    assert_eq!(graph.order(), 8);
+    assert_eq!(graph.edge_count(), 16);
+    // 011 → 110 and 011 → 111
+    assert!(graph.has_edge(3, 6) && graph.has_edge(3, 7));
+    assert!(graph.has_edge(0, 0) && graph.has_edge(7, 7));
+  }
+
+  #[test]
+  fn kautz_has_no_self_loops() {
+    let graph = kautz(2, 3);
+
+    assert_eq!(graph.order(), 3 * 2 * 2);
+    assert_eq!(graph.edge_count(), 3 * 2 * 2 * 2);
+    assert!(graph.all_edges().iter().all(|(u, v)| u != v));
+    // 010 → 101 and 010 → 102, words 0 to 11: 010 011 020 021 101 102 120 121 201 202 210 212
+    assert!(graph.has_edge(0, 4) && graph.has_edge(0, 5));
+  }
+
+  #[test]
+  fn orders_detect_overflow() {
+    assert_eq!(de_bruijn_order(2, 3), Some(8));
+    assert_eq!(de_bruijn_order(2, 0), None);
+    assert_eq!(kautz_order(2, 3), Some(12));
+    assert_eq!(de_bruijn_order(2, 32), None);
+    assert_eq!(de_bruijn_order(10, 100), None);
+    assert_eq!(kautz_order(3, 40), None);
+  }
+
+  #[test]
+  #[should_panic]
+  fn de_bruijn_needs_words() {
+    de_bruijn(2, 0);
+  }
+}
@@ -0,0 +1,32 @@
+use crate::graph::hash_table::{HashTable, VertexId};
+
+/// Directed grid on a torus: vertex r * columns + c points to its right and lower neighbor, the
+/// last column to the first and the last row to the first. Every row and every column is a directed
+/// cycle, so the FAS has at least max(rows, columns) arcs.
+pub fn toroidal_grid(rows: usize, columns: usize) -> HashTable {
+  let id = |r: usize, c: usize| (r * columns + c) as VertexId;
+  let vertices = (0..(rows * columns) as VertexId).collect::<Vec<_>>();
+  let mut edges = vec![];
+  for r in 0..rows {
+    for c in 0..columns {
+      edges.push((id(r, c), id(r, (c + 1) % columns)));
+      edges.push((id(r, c), id((r + 1) % rows, c)));
+    }
+  }
+  HashTable::from_vertices_and_edges(&vertices, &edges)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::generator::grid::toroidal_grid;
+
+  #[test]
+  fn every_vertex_has_two_successors() {
+    let graph = toroidal_grid(4, 5);
+
+    assert_eq!(graph.order(), 20);
+    assert_eq!(graph.edge_count(), 40);
+    assert!(graph.has_edge(4, 0) && graph.has_edge(15, 0));
+    assert!(graph.is_cyclic());
+  }
+}
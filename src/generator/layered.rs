@@ -0,0 +1,51 @@
+use crate::graph::hash_table::{HashTable, VertexId};
+use rand::Rng;
+
+/// Layered DAG with back-arc noise: the vertex l * width + i is the i-th vertex of layer l, an arc
+/// between consecutive layers exists with probability density and is reversed with probability
+/// noise. The reversed arcs are a FAS.
+pub fn layered_with_noise<R: Rng>(
+  layers: usize,
+  width: usize,
+  density: f64,
+  noise: f64,
+  rng: &mut R,
+) -> HashTable {
+  assert!((0.0..=1.0).contains(&density));
+  assert!((0.0..=1.0).contains(&noise));
+  let id = |l: usize, i: usize| (l * width + i) as VertexId;
+  let vertices = (0..(layers * width) as VertexId).collect::<Vec<_>>();
+  let mut edges = vec![];
+  for l in 1..layers {
+    for i in 0..width {
+      for j in 0..width {
+        if !rng.gen_bool(density) {
+          continue;
+        }
+        let (u, v) = (id(l - 1, i), id(l, j));
+        edges.push(if rng.gen_bool(noise) { (v, u) } else { (u, v) });
+      }
+    }
+  }
+  HashTable::from_vertices_and_edges(&vertices, &edges)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::generator::layered::layered_with_noise;
+  use rand::rngs::StdRng;
+  use rand::SeedableRng;
+
+  #[test]
+  fn arcs_join_consecutive_layers() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let graph = layered_with_noise(6, 8, 0.5, 0.1, &mut rng);
+
+    assert_eq!(graph.order(), 48);
+    assert!(graph
+      .all_edges()
+      .iter()
+      .all(|(u, v)| (*u / 8).abs_diff(*v / 8) == 1));
+    assert!(!layered_with_noise(6, 8, 0.5, 0.0, &mut rng).is_cyclic());
+  }
+}
@@ -0,0 +1,6 @@
+pub mod de_bruijn;
+pub mod grid;
+pub mod layered;
+pub mod planted;
+pub mod preferential_attachment;
+pub mod tournament;
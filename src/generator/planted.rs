@@ -0,0 +1,223 @@
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use crate::tools::manifest::ManifestEntry;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
+
+/*
+Random graph with a planted feedback arc set whose optimality is certified by arc-disjoint cycles:
+
+π := random permutation of the vertices              {hidden ordering}
+repeat k times
+    choose positions p0 < p1 < ... < pl at random   {l = cycle_length - 1}
+    if none of the arcs π(p0) → π(p1) → ... → π(pl) belongs to an earlier witness cycle
+        add this path and the backward arc π(pl) → π(p0)
+for all positions i < j
+    add the forward arc π(i) → π(j) with probability p      {geometric skips over the other pairs}
+
+Removing the k backward arcs leaves only forward arcs, i.e. a DAG. Every FAS has to contain an
+arc of each of the k arc-disjoint witness cycles, so no FAS has less than k arcs.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlantedFas {
+  pub vertices: usize,
+  /// Probability of every additional forward arc
+  pub density: f64,
+  /// Size of the planted feedback arc set
+  pub planted: usize,
+  /// Number of arcs of every witness cycle, at least 2
+  pub cycle_length: usize,
+}
+
+/// A generated graph together with its optimal feedback arc set
+#[derive(Clone, Debug)]
+pub struct PlantedInstance {
+  pub graph: HashTable,
+  /// The backward arcs, an optimal FAS
+  pub fas: HashSet<Edge>,
+  /// The hidden ordering, all other arcs point forwards
+  pub ordering: Vec<VertexId>,
+  /// Arc-disjoint cycles, each closed by one arc of the FAS, as vertex sequences
+  pub cycles: Vec<Vec<VertexId>>,
+}
+
+impl PlantedFas {
+  pub fn new(vertices: usize, density: f64, planted: usize) -> Self {
+    Self {
+      vertices,
+      density,
+      planted,
+      cycle_length: 3,
+    }
+  }
+
+  pub fn generate<R: Rng>(&self, rng: &mut R) -> PlantedInstance {
+    assert!((0.0..=1.0).contains(&self.density));
+    assert!(self.cycle_length >= 2);
+    assert!(
+      self.cycle_length <= self.vertices || self.planted == 0,
+      "A witness cycle needs {} vertices",
+      self.cycle_length
+    );
+
+    let mut ordering = (0..self.vertices as VertexId).collect::<Vec<_>>();
+    ordering.shuffle(rng);
+    let mut graph = HashTable::from_vertices_and_edges(&ordering, &[]);
+    let mut fas = HashSet::new();
+    let mut cycles = vec![];
+    let mut witness_arcs = HashSet::new();
+
+    // Dense or small graphs run out of disjoint paths, so the number of tries is bounded
+    let mut tries = 0;
+    while cycles.len() < self.planted {
+      tries += 1;
+      assert!(
+        tries <= 100 * self.planted,
+        "Cannot find {} arc-disjoint witness cycles",
+        self.planted
+      );
+
+      let mut positions =
+        rand::seq::index::sample(rng, self.vertices, self.cycle_length).into_vec();
+      positions.sort_unstable();
+      let cycle = positions.iter().map(|p| ordering[*p]).collect::<Vec<_>>();
+      let path = cycle.windows(2).map(|w| (w[0], w[1])).collect::<Vec<_>>();
+      let backward = (cycle[cycle.len() - 1], cycle[0]);
+      if fas.contains(&backward) || path.iter().any(|e| witness_arcs.contains(e)) {
+        continue;
+      }
+
+      for e in path {
+        witness_arcs.insert(e);
+        graph.add_edge(e);
+      }
+      fas.insert(backward);
+      graph.add_edge(backward);
+      cycles.push(cycle);
+    }
+
+    for (i, j) in random_pairs(self.vertices, self.density, rng) {
+      graph.add_edge((ordering[i], ordering[j]));
+    }
+
+    PlantedInstance {
+      graph,
+      fas,
+      ordering,
+      cycles,
+    }
+  }
+}
+
+/// Every pair of positions i < j with probability p, in O(n + number of pairs) expected time.
+/// The gap to the next chosen pair is geometrically distributed, so the other pairs are skipped.
+fn random_pairs<R: Rng>(n: usize, p: f64, rng: &mut R) -> Vec<(usize, usize)> {
+  let mut pairs = vec![];
+  if p <= 0.0 {
+    return pairs;
+  }
+  // (i, j) is the last pair considered, starting before (0, 1)
+  let (mut i, mut j) = (0_usize, 0_usize);
+  loop {
+    let skip = if p >= 1.0 {
+      0
+    } else {
+      ((1.0 - rng.gen::<f64>()).ln() / (1.0 - p).ln()).floor() as usize
+    };
+    j = j.saturating_add(skip + 1);
+    // Row i contains the pairs (i, i + 1), ..., (i, n - 1)
+    while i < n && j >= n {
+      j = (j - n).saturating_add(i + 2);
+      i += 1;
+    }
+    if i >= n {
+      return pairs;
+    }
+    pairs.push((i, j));
+  }
+}
+
+impl PlantedInstance {
+  /// Entry for the quality harness, the planted FAS is optimal
+  pub fn manifest_entry(&self, instance: &str) -> ManifestEntry {
+    ManifestEntry {
+      instance: instance.to_string(),
+      vertex_count: self.graph.order(),
+      arc_count: self.graph.edge_count(),
+      fas_size: self.fas.len(),
+      is_optimal: true,
+      tolerance: 0.0,
+      source: "planted".to_string(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::fas::feedback_arc_set::FeedbackArcSet;
+  use crate::fas::greedy::GreedyHeuristic;
+  use crate::generator::planted::{random_pairs, PlantedFas};
+  use crate::ordering::topological_sort::leftward_edges;
+  use rand::rngs::StdRng;
+  use rand::SeedableRng;
+  use std::collections::HashSet;
+
+  #[test]
+  fn planted_fas_is_leftward_set_of_ordering() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let instance = PlantedFas::new(200, 0.05, 30).generate(&mut rng);
+
+    assert_eq!(instance.fas.len(), 30);
+    assert_eq!(
+      leftward_edges(&instance.graph, instance.ordering.clone()),
+      instance.fas
+    );
+  }
+
+  #[test]
+  fn witness_cycles_are_arc_disjoint() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let generator = PlantedFas {
+      cycle_length: 5,
+      ..PlantedFas::new(50, 0.1, 20)
+    };
+    let instance = generator.generate(&mut rng);
+
+    let mut arcs = HashSet::new();
+    for cycle in &instance.cycles {
+      assert_eq!(cycle.len(), 5);
+      let closing = (cycle[4], cycle[0]);
+      assert!(instance.fas.contains(&closing));
+      for e in cycle.windows(2).map(|w| (w[0], w[1])).chain([closing]) {
+        assert!(instance.graph.has_edge(e.0, e.1));
+        assert!(arcs.insert(e));
+      }
+    }
+  }
+
+  #[test]
+  fn random_pairs_skip_geometrically() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let all = (0..5)
+      .flat_map(|i| ((i + 1)..5).map(move |j| (i, j)))
+      .collect::<Vec<_>>();
+    assert_eq!(random_pairs(5, 1.0, &mut rng), all);
+    assert!(random_pairs(5, 0.0, &mut rng).is_empty());
+
+    let pairs = random_pairs(2000, 0.01, &mut rng);
+    let expected = 0.01 * (2000.0 * 1999.0 / 2.0);
+    assert!((pairs.len() as f64 - expected).abs() < 0.05 * expected);
+    assert!(pairs.windows(2).all(|w| w[0] < w[1]));
+    assert!(pairs.iter().all(|(i, j)| i < j && *j < 2000));
+  }
+
+  #[test]
+  fn heuristics_find_at_least_planted_size() {
+    let mut rng = StdRng::seed_from_u64(2);
+    let instance = PlantedFas::new(300, 0.02, 40).generate(&mut rng);
+    let fas = GreedyHeuristic::new(&instance.graph).feedback_arc_set();
+
+    assert!(fas.len() >= instance.fas.len());
+    assert_eq!(instance.manifest_entry("planted").fas_size, 40);
+  }
+}
@@ -0,0 +1,72 @@
+use crate::graph::hash_table::{HashTable, VertexId};
+use rand::Rng;
+
+/*
+Directed preferential attachment (scale-free), similar to Barabási and Albert:
+
+start with a directed cycle on degree + 1 vertices
+for every further vertex v
+    choose degree distinct older vertices u, each with probability ∝ degree(u)
+    add v → u or u → v, each with probability 1/2
+
+Without the random direction all arcs would point to older vertices and the graph would be acyclic.
+ */
+pub fn preferential_attachment<R: Rng>(n: usize, degree: usize, rng: &mut R) -> HashTable {
+  assert!(degree > 0);
+  let initial = (degree + 1).min(n);
+  let vertices = (0..n as VertexId).collect::<Vec<_>>();
+  let mut graph = HashTable::from_vertices_and_edges(&vertices, &[]);
+  // Every vertex appears once per incident arc, so a uniform choice is proportional to the degree
+  let mut endpoints = vec![];
+
+  if initial > 1 {
+    for u in 0..initial as VertexId {
+      let v = (u + 1) % initial as VertexId;
+      graph.add_edge((u, v));
+      endpoints.extend([u, v]);
+    }
+  }
+
+  for v in initial as VertexId..n as VertexId {
+    let mut targets: Vec<VertexId> = vec![];
+    while targets.len() < degree {
+      let u = endpoints[rng.gen_range(0..endpoints.len())];
+      if !targets.contains(&u) {
+        targets.push(u);
+      }
+    }
+    for u in targets {
+      graph.add_edge(if rng.gen_bool(0.5) { (v, u) } else { (u, v) });
+      endpoints.extend([u, v]);
+    }
+  }
+  graph
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::generator::preferential_attachment::preferential_attachment;
+  use crate::graph::hash_table::Direction;
+  use rand::rngs::StdRng;
+  use rand::SeedableRng;
+
+  #[test]
+  fn adds_degree_arcs_per_vertex() {
+    let graph = preferential_attachment(500, 3, &mut StdRng::seed_from_u64(0));
+
+    assert_eq!(graph.order(), 500);
+    assert_eq!(graph.edge_count(), 4 + 3 * (500 - 4));
+    assert!(graph.is_cyclic());
+  }
+
+  #[test]
+  fn old_vertices_become_hubs() {
+    let graph = preferential_attachment(2000, 2, &mut StdRng::seed_from_u64(1));
+    let degree =
+      |v| graph.edges(v, Direction::Outbound).len() + graph.edges(v, Direction::Inbound).len();
+    let max_degree = graph.vertices().into_iter().map(degree).max().unwrap();
+
+    // A graph without preferential attachment would have a maximum degree near 2 * 2 + log(n)
+    assert!(max_degree > 40);
+  }
+}
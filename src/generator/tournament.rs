@@ -0,0 +1,49 @@
+use crate::graph::hash_table::{HashTable, VertexId};
+use rand::Rng;
+
+/// Tournament on the vertices 0..n, every pair is joined by an arc of random direction
+pub fn random_tournament<R: Rng>(n: usize, rng: &mut R) -> HashTable {
+  near_transitive_tournament(n, 0.5, rng)
+}
+
+/// Transitive tournament on 0..n (all arcs point to the larger id), in which every arc is reversed
+/// with probability noise. The reversed arcs are a FAS, so for small noise the optimum is near
+/// noise * n (n - 1) / 2.
+pub fn near_transitive_tournament<R: Rng>(n: usize, noise: f64, rng: &mut R) -> HashTable {
+  assert!((0.0..=1.0).contains(&noise));
+  let vertices = (0..n as VertexId).collect::<Vec<_>>();
+  let mut edges = vec![];
+  for u in 0..n as VertexId {
+    for v in (u + 1)..n as VertexId {
+      edges.push(if rng.gen_bool(noise) { (v, u) } else { (u, v) });
+    }
+  }
+  HashTable::from_vertices_and_edges(&vertices, &edges)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::generator::tournament::{near_transitive_tournament, random_tournament};
+  use rand::rngs::StdRng;
+  use rand::SeedableRng;
+
+  #[test]
+  fn every_pair_has_one_arc() {
+    let graph = random_tournament(30, &mut StdRng::seed_from_u64(0));
+
+    assert_eq!(graph.order(), 30);
+    assert_eq!(graph.edge_count(), 30 * 29 / 2);
+    assert!(graph
+      .all_edges()
+      .iter()
+      .all(|(u, v)| !graph.has_edge(*v, *u)));
+  }
+
+  #[test]
+  fn without_noise_tournament_is_transitive() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    assert!(!near_transitive_tournament(20, 0.0, &mut rng).is_cyclic());
+    assert!(near_transitive_tournament(20, 0.2, &mut rng).is_cyclic());
+  }
+}
@@ -20,6 +20,12 @@ pub struct HashTable {
 
 impl GraphDataStructure for HashTable {}
 
+impl Default for HashTable {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
 impl HashTable {
   // ======= Creational Methods =======
 
@@ -102,12 +108,12 @@ impl HashTable {
   }
 
   pub fn edge_count(&self) -> usize {
-    self.data.iter().map(|(_, edges)| edges.len()).sum()
+    self.data.values().map(|edges| edges.len()).sum()
   }
 
   // Returns all vertices
   pub fn vertices(&self) -> Vec<VertexId> {
-    self.data.clone().into_keys().collect()
+    self.data.keys().copied().collect()
   }
 
   // Returns all edges of a vertex for a specified direction
@@ -163,17 +169,17 @@ impl HashTable {
   // ======= Mutating Methods =======
 
   fn add_vertex(&mut self, v: VertexId) {
-    self.data.entry(v).or_insert_with(Vec::new);
+    self.data.entry(v).or_default();
   }
 
   /// Adds the directed edge (u, v)
   pub fn add_edge(&mut self, e: Edge) {
-    let edges = self.data.entry(e.0).or_insert_with(Vec::new);
+    let edges = self.data.entry(e.0).or_default();
     if !edges.contains(&e.1) {
       edges.push(e.1);
     }
 
-    self.data.entry(e.1).or_insert_with(Vec::new);
+    self.data.entry(e.1).or_default();
   }
 
   pub fn remove_vertex(&mut self, v: VertexId) {
@@ -187,15 +193,12 @@ impl HashTable {
   }
 
   pub fn remove_edge(&mut self, e: Edge) {
-    match self.data.get_mut(&e.0) {
-      Some(edges) => {
-        edges
-          .iter()
-          .position(|&neighbor| neighbor == e.1)
-          .map(|index| edges.remove(index));
-      }
-      None => (),
-    };
+    if let Some(edges) = self.data.get_mut(&e.0) {
+      edges
+        .iter()
+        .position(|&neighbor| neighbor == e.1)
+        .map(|index| edges.remove(index));
+    }
   }
 
   // Returns all edges that start in from_partition and end in to_partition
@@ -218,8 +221,8 @@ impl HashTable {
 
   // ======= Algorithm Methods =======
 
-  pub fn random_vertex(&self) -> VertexId {
-    let idx = rand::thread_rng().gen_range(0..self.data.len());
+  pub fn random_vertex<R: Rng>(&self, rng: &mut R) -> VertexId {
+    let idx = rng.gen_range(0..self.data.len());
     self.data.keys().nth(idx).copied().unwrap()
   }
 }
@@ -1 +1,3 @@
 pub mod hash_table;
+pub mod reorientation;
+pub mod statistics;
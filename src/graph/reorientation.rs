@@ -0,0 +1,137 @@
+use crate::graph::hash_table::{Edge, HashTable};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
+/*
+Acyclic reorientation, as used for cycle removal in layered drawing and in ranking: instead of
+deleting the arcs of a feedback arc set F, they are reversed.
+
+for every arc u → v of G with weight w
+    if u = v then drop it                   {a self-loop can not be reversed}
+    else if u → v ∈ F then add w to v → u   {merges with an existing arc v → u}
+    else add w to u → v
+
+Reversing an inclusion-minimal FAS always gives a DAG. A redundant arc of F can close a cycle when
+it is reversed, so such an F is rejected (see fas::minimal::make_minimal).
+ */
+#[derive(Clone, Debug)]
+pub struct Reorientation {
+  pub dag: HashTable,
+  /// Weight of every arc of the DAG, the sum of the weights of the arcs of G it stands for
+  pub weights: BTreeMap<Edge, u64>,
+  /// The arcs of the FAS in their original direction, which were reversed
+  pub flipped: BTreeSet<Edge>,
+  /// Self-loops of the FAS, which were dropped
+  pub dropped: BTreeSet<Edge>,
+}
+
+impl Reorientation {
+  /// Reorients a graph whose arcs all weigh 1
+  pub fn new(graph: &HashTable, fas: &HashSet<Edge>) -> Result<Self, String> {
+    Self::with_weights(graph, &HashMap::new(), fas)
+  }
+
+  /// Arcs without a weight weigh 1
+  pub fn with_weights(
+    graph: &HashTable,
+    weights: &HashMap<Edge, u64>,
+    fas: &HashSet<Edge>,
+  ) -> Result<Self, String> {
+    if let Some(e) = fas.iter().find(|(u, v)| !graph.has_edge(*u, *v)) {
+      return Err(format!("{e:?} is not an arc of the graph"));
+    }
+
+    let mut reorientation = Self {
+      dag: HashTable::from_vertices_and_edges(&graph.vertices(), &[]),
+      weights: BTreeMap::new(),
+      flipped: BTreeSet::new(),
+      dropped: BTreeSet::new(),
+    };
+    for (u, v) in graph.all_edges() {
+      let weight = weights.get(&(u, v)).copied().unwrap_or(1);
+      let arc = if u == v {
+        if fas.contains(&(u, v)) {
+          reorientation.dropped.insert((u, v));
+          continue;
+        }
+        (u, v)
+      } else if fas.contains(&(u, v)) {
+        reorientation.flipped.insert((u, v));
+        (v, u)
+      } else {
+        (u, v)
+      };
+      reorientation.dag.add_edge(arc);
+      *reorientation.weights.entry(arc).or_default() += weight;
+    }
+
+    if reorientation.dag.is_cyclic() {
+      return Err(
+        "Reversing the feedback arc set closes a cycle, it is not minimal (see make_minimal)"
+          .to_string(),
+      );
+    }
+    Ok(reorientation)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::fas::feedback_arc_set::FeedbackArcSet;
+  use crate::fas::greedy::GreedyHeuristic;
+  use crate::fas::minimal::make_minimal;
+  use crate::graph::hash_table::HashTable;
+  use crate::graph::reorientation::Reorientation;
+  use crate::tools::graphs::{graph_from_file, graph_with_simple_clique};
+  use std::collections::{BTreeSet, HashMap, HashSet};
+
+  #[test]
+  fn reverses_arcs() {
+    let clique = graph_with_simple_clique();
+    let reorientation = Reorientation::new(&clique, &HashSet::from([(2, 0)])).unwrap();
+
+    assert_eq!(reorientation.dag.all_edges(), vec![(0, 1), (0, 2), (1, 2)]);
+    assert_eq!(reorientation.flipped, BTreeSet::from([(2, 0)]));
+    assert!(reorientation.weights.values().all(|w| *w == 1));
+  }
+
+  #[test]
+  fn merges_with_opposite_arc() {
+    let graph = HashTable::from_edges(&[(0, 1), (1, 0), (1, 2), (1, 1)]);
+    let weights = HashMap::from([((0, 1), 2), ((1, 0), 3)]);
+    let fas = HashSet::from([(1, 0), (1, 1)]);
+    let reorientation = Reorientation::with_weights(&graph, &weights, &fas).unwrap();
+
+    assert_eq!(reorientation.dag.all_edges(), vec![(0, 1), (1, 2)]);
+    assert_eq!(reorientation.weights[&(0, 1)], 5);
+    assert_eq!(reorientation.weights[&(1, 2)], 1);
+    assert_eq!(reorientation.flipped, BTreeSet::from([(1, 0)]));
+    assert_eq!(reorientation.dropped, BTreeSet::from([(1, 1)]));
+  }
+
+  #[test]
+  fn rejects_non_minimal_fas() {
+    // Reversing the redundant arc 0 → 2 closes the cycle 0 → 1 → 2 → 0
+    let graph = HashTable::from_edges(&[(0, 1), (1, 2), (0, 2)]);
+
+    assert!(Reorientation::new(&graph, &HashSet::from([(0, 2)])).is_err());
+  }
+
+  #[test]
+  fn rejects_unknown_arc() {
+    let clique = graph_with_simple_clique();
+    assert!(Reorientation::new(&clique, &HashSet::from([(0, 2)])).is_err());
+  }
+
+  #[test]
+  fn keeps_total_weight() {
+    let graph = graph_from_file("h_001");
+    let fas = make_minimal(&graph, &GreedyHeuristic::new(&graph).feedback_arc_set());
+    let reorientation = Reorientation::new(&graph, &fas).unwrap();
+
+    assert_eq!(reorientation.flipped.len(), fas.len());
+    assert_eq!(
+      reorientation.weights.values().sum::<u64>(),
+      graph.edge_count() as u64
+    );
+  }
+}
@@ -0,0 +1,202 @@
+use crate::graph::hash_table::HashTable;
+use crate::scc::strongly_connected_components::StronglyConnectedComponents;
+use crate::scc::tarjan::Tarjan;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter, Result};
+
+/// Key figures of a graph that help to choose a feedback arc set algorithm.
+/// All distributions are histograms, i.e. value -> number of occurrences.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphStatistics {
+  pub vertex_count: usize,
+  pub arc_count: usize,
+  pub in_degrees: BTreeMap<usize, usize>,
+  pub out_degrees: BTreeMap<usize, usize>,
+  pub self_loops: usize,
+  /// Number of vertex pairs u, v with both arcs (u, v) and (v, u)
+  pub two_cycles: usize,
+  pub scc_count: usize,
+  pub scc_sizes: BTreeMap<usize, usize>,
+  pub largest_scc: usize,
+  /// Arcs divided by the number of possible arcs without self-loops, n * (n - 1)
+  pub density: f64,
+  pub is_acyclic: bool,
+}
+
+impl GraphStatistics {
+  pub fn new(graph: &HashTable) -> Self {
+    let vertices = graph.vertices();
+    let arcs = graph.all_edges();
+
+    let mut in_degree = vertices
+      .iter()
+      .map(|v| (*v, 0))
+      .collect::<HashMap<_, usize>>();
+    arcs
+      .iter()
+      .for_each(|(_, destination)| *in_degree.get_mut(destination).unwrap() += 1);
+
+    let self_loops = arcs.iter().filter(|(u, v)| u == v).count();
+    let two_cycles = arcs
+      .iter()
+      .filter(|(u, v)| u < v && graph.has_edge(*v, *u))
+      .count();
+
+    let sccs = Tarjan::new(graph).strongly_connected_components();
+    let scc_sizes = histogram(sccs.iter().map(|scc| scc.len()));
+    let largest_scc = sccs.iter().map(|scc| scc.len()).max().unwrap_or(0);
+
+    let n = vertices.len();
+    let density = if n > 1 {
+      arcs.len() as f64 / (n * (n - 1)) as f64
+    } else {
+      0.0
+    };
+
+    Self {
+      vertex_count: n,
+      arc_count: arcs.len(),
+      in_degrees: histogram(in_degree.into_values()),
+      out_degrees: histogram(vertices.iter().map(|v| graph.degree(*v))),
+      self_loops,
+      two_cycles,
+      scc_count: sccs.len(),
+      scc_sizes,
+      largest_scc,
+      density,
+      // A graph is acyclic iff all SCCs are single vertices without self-loops
+      is_acyclic: largest_scc <= 1 && self_loops == 0,
+    }
+  }
+
+  pub fn to_json(&self) -> String {
+    let json_histogram = |histogram: &BTreeMap<usize, usize>| {
+      let entries = histogram
+        .iter()
+        .map(|(value, count)| format!("\"{value}\":{count}"))
+        .collect::<Vec<_>>();
+      format!("{{{}}}", entries.join(","))
+    };
+
+    format!(
+      "{{\"vertices\":{},\"arcs\":{},\"density\":{:.6},\"self_loops\":{},\"two_cycles\":{},\"acyclic\":{},\"scc_count\":{},\"largest_scc\":{},\"scc_sizes\":{},\"in_degrees\":{},\"out_degrees\":{}}}",
+      self.vertex_count,
+      self.arc_count,
+      self.density,
+      self.self_loops,
+      self.two_cycles,
+      self.is_acyclic,
+      self.scc_count,
+      self.largest_scc,
+      json_histogram(&self.scc_sizes),
+      json_histogram(&self.in_degrees),
+      json_histogram(&self.out_degrees)
+    )
+  }
+}
+
+impl Display for GraphStatistics {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    let text_histogram = |histogram: &BTreeMap<usize, usize>| {
+      histogram
+        .iter()
+        .map(|(value, count)| format!("{value}: {count}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+    };
+
+    writeln!(f, "vertices:    {}", self.vertex_count)?;
+    writeln!(f, "arcs:        {}", self.arc_count)?;
+    writeln!(f, "density:     {:.6}", self.density)?;
+    writeln!(f, "self-loops:  {}", self.self_loops)?;
+    writeln!(f, "2-cycles:    {}", self.two_cycles)?;
+    writeln!(
+      f,
+      "acyclic:     {}",
+      if self.is_acyclic { "yes" } else { "no" }
+    )?;
+    writeln!(f, "SCCs:        {}", self.scc_count)?;
+    writeln!(f, "largest SCC: {}", self.largest_scc)?;
+    writeln!(f, "SCC sizes:   {}", text_histogram(&self.scc_sizes))?;
+    writeln!(f, "in-degrees:  {}", text_histogram(&self.in_degrees))?;
+    writeln!(f, "out-degrees: {}", text_histogram(&self.out_degrees))
+  }
+}
+
+fn histogram<I: Iterator<Item = usize>>(values: I) -> BTreeMap<usize, usize> {
+  values.fold(BTreeMap::new(), |mut histogram, value| {
+    *histogram.entry(value).or_default() += 1;
+    histogram
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::graph::hash_table::HashTable;
+  use crate::graph::statistics::GraphStatistics;
+  use crate::tools::graphs::{graph_from_file, graph_from_wikipedia_scc};
+  use std::collections::BTreeMap;
+
+  #[test]
+  fn works_on_wikipedia_scc() {
+    let statistics = GraphStatistics::new(&graph_from_wikipedia_scc());
+
+    assert_eq!(statistics.vertex_count, 8);
+    assert_eq!(statistics.arc_count, 14);
+    assert_eq!(statistics.self_loops, 0);
+    // (3, 4), (4, 8) and (6, 7)
+    assert_eq!(statistics.two_cycles, 3);
+    assert_eq!(statistics.scc_count, 3);
+    assert_eq!(statistics.scc_sizes, BTreeMap::from([(2, 1), (3, 2)]));
+    assert_eq!(statistics.largest_scc, 3);
+    assert_eq!(statistics.density, 14.0 / 56.0);
+    assert!(!statistics.is_acyclic);
+  }
+
+  #[test]
+  fn works_on_dag_with_self_loop() {
+    let mut graph = HashTable::from_edges(&[(0, 1), (1, 2), (0, 2)]);
+    assert!(GraphStatistics::new(&graph).is_acyclic);
+
+    graph.add_edge((2, 2));
+    let statistics = GraphStatistics::new(&graph);
+
+    assert_eq!(statistics.self_loops, 1);
+    assert!(!statistics.is_acyclic);
+    assert_eq!(
+      statistics.in_degrees,
+      BTreeMap::from([(0, 1), (1, 1), (3, 1)])
+    );
+    assert_eq!(statistics.out_degrees, BTreeMap::from([(1, 2), (2, 1)]));
+  }
+
+  #[test]
+  fn json_is_stable() {
+    let graph = HashTable::from_edges(&[(0, 1), (1, 0), (1, 2)]);
+
+    assert_eq!(
+      GraphStatistics::new(&graph).to_json(),
+      "{\"vertices\":3,\"arcs\":3,\"density\":0.500000,\"self_loops\":0,\"two_cycles\":1,\"acyclic\":false,\"scc_count\":2,\"largest_scc\":2,\"scc_sizes\":{\"1\":1,\"2\":1},\"in_degrees\":{\"1\":3},\"out_degrees\":{\"0\":1,\"1\":1,\"2\":1}}"
+    );
+  }
+
+  #[test]
+  fn degree_distributions_sum_up() {
+    let statistics = GraphStatistics::new(&graph_from_file("h_001"));
+
+    assert_eq!(statistics.vertex_count, 1024);
+    assert_eq!(statistics.arc_count, 2103);
+    for histogram in [&statistics.in_degrees, &statistics.out_degrees] {
+      assert_eq!(histogram.values().sum::<usize>(), 1024);
+      assert_eq!(histogram.iter().map(|(d, c)| d * c).sum::<usize>(), 2103);
+    }
+    assert_eq!(
+      statistics
+        .scc_sizes
+        .iter()
+        .map(|(s, c)| s * c)
+        .sum::<usize>(),
+      1024
+    );
+  }
+}
@@ -0,0 +1,210 @@
+/*
+Coffman–Graham layering with at most width vertices per layer:
+
+{labeling}
+for i := 1 to |V|
+    among the unlabeled vertices whose predecessors are all labeled, choose v whose predecessor
+    labels, sorted in decreasing order, are lexicographically smallest
+    label(v) := i
+{layering, bottom-up}
+k := 1; L1 := ∅
+while not all vertices are placed
+    among the unplaced vertices whose successors are all placed, choose u with maximal label(u)
+    if |Lk| = width or a successor of u is in Lk then k := k + 1; Lk := ∅
+    Lk := Lk ∪ {u}
+
+The number of layers is at most (2 - 2 / width) times the optimum for the given width, if the graph is
+transitively reduced. Layers are numbered from the top in the result, so layer(u) = k_max - k.
+Both phases keep the vertices that may be chosen next in a priority queue. The predecessor labels
+of a vertex are fixed once it enters the queue, so the labeling takes O(|V| log |V|) comparisons of
+label lists and O(|E| log |E|) for sorting them, the layering takes O((|V| + |E|) log |V|).
+ */
+
+use crate::graph::hash_table::{HashTable, VertexId};
+use crate::layering::layer_assignment::{LayerAssignment, Layering};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+pub struct CoffmanGraham<'a> {
+  pub graph: &'a HashTable,
+  /// Maximum number of vertices per layer
+  pub width: usize,
+}
+
+impl<'a> CoffmanGraham<'a> {
+  pub fn new(graph: &'a HashTable, width: usize) -> Self {
+    assert!(width > 0);
+    Self { graph, width }
+  }
+
+  fn predecessors(&self) -> HashMap<VertexId, Vec<VertexId>> {
+    let mut predecessors = HashMap::<VertexId, Vec<VertexId>>::new();
+    for (u, v) in self.graph.all_edges() {
+      predecessors.entry(v).or_default().push(u);
+    }
+    predecessors
+  }
+
+  fn labels(&self) -> HashMap<VertexId, usize> {
+    let predecessors = self.predecessors();
+    let mut unlabeled_predecessors = self
+      .graph
+      .vertices()
+      .into_iter()
+      .map(|v| (v, predecessors.get(&v).map_or(0, Vec::len)))
+      .collect::<HashMap<_, _>>();
+
+    // Ready vertices with their predecessor labels in decreasing order, the smallest list first
+    let mut ready = unlabeled_predecessors
+      .iter()
+      .filter(|(_, count)| **count == 0)
+      .map(|(v, _)| Reverse((vec![], *v)))
+      .collect::<BinaryHeap<Reverse<(Vec<usize>, VertexId)>>>();
+    let mut labels = HashMap::new();
+
+    while let Some(Reverse((_, v))) = ready.pop() {
+      labels.insert(v, labels.len() + 1);
+      for w in self.graph.neighborhood(&v) {
+        let count = unlabeled_predecessors.get_mut(w).unwrap();
+        *count -= 1;
+        if *count == 0 {
+          let mut predecessor_labels = predecessors[w]
+            .iter()
+            .map(|u| labels[u])
+            .collect::<Vec<_>>();
+          predecessor_labels.sort_unstable_by(|a, b| b.cmp(a));
+          ready.push(Reverse((predecessor_labels, *w)));
+        }
+      }
+    }
+
+    assert_eq!(
+      labels.len(),
+      self.graph.order(),
+      "Layer assignment needs an acyclic graph"
+    );
+    labels
+  }
+}
+
+impl<'a> LayerAssignment for CoffmanGraham<'a> {
+  fn layering(&self) -> Layering {
+    let labels = self.labels();
+    let predecessors = self.predecessors();
+    let mut unplaced_successors = labels
+      .keys()
+      .map(|u| (*u, self.graph.neighborhood(u).len()))
+      .collect::<HashMap<_, _>>();
+
+    // Ready vertices by their label, the largest first
+    let mut ready = unplaced_successors
+      .iter()
+      .filter(|(_, count)| **count == 0)
+      .map(|(u, _)| (labels[u], *u))
+      .collect::<BinaryHeap<_>>();
+    let mut from_bottom = HashMap::<VertexId, usize>::new();
+    let mut k = 0;
+    let mut size_of_k = 0;
+
+    while let Some((_, u)) = ready.pop() {
+      let successor_in_k = self
+        .graph
+        .neighborhood(&u)
+        .iter()
+        .any(|v| from_bottom[v] == k);
+      if size_of_k == self.width || successor_in_k {
+        k += 1;
+        size_of_k = 0;
+      }
+      from_bottom.insert(u, k);
+      size_of_k += 1;
+
+      for w in predecessors.get(&u).into_iter().flatten() {
+        let count = unplaced_successors.get_mut(w).unwrap();
+        *count -= 1;
+        if *count == 0 {
+          ready.push((labels[w], *w));
+        }
+      }
+    }
+
+    from_bottom
+      .into_iter()
+      .map(|(u, layer)| (u, k - layer))
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::fas::feedback_arc_set::FeedbackArcSet;
+  use crate::fas::greedy::GreedyHeuristic;
+  use crate::graph::hash_table::HashTable;
+  use crate::layering::coffman_graham::CoffmanGraham;
+  use crate::layering::layer_assignment::{is_valid, width, LayerAssignment, Layering};
+  use crate::layering::longest_path::LongestPath;
+  use crate::tools::graphs::{graph_from_file, graph_with_multiple_cliques};
+
+  #[test]
+  fn bounds_width() {
+    // One source with six successors
+    let graph = HashTable::from_edges(&(1..=6).map(|v| (0, v)).collect::<Vec<_>>());
+
+    for w in 1..=6 {
+      let layering = CoffmanGraham::new(&graph, w).layering();
+      assert!(is_valid(&graph, &layering));
+      assert!(width(&layering) <= w);
+      assert_eq!(
+        layering.values().max().unwrap() + 1,
+        1 + 6_usize.div_ceil(w),
+        "width {w}"
+      );
+    }
+  }
+
+  #[test]
+  fn labels_by_predecessor_labels() {
+    // 0 and 1 are sources, 2 and 3 both have a predecessor, but 2's is labeled first
+    let graph = HashTable::from_edges(&[(0, 2), (1, 3), (2, 4), (3, 4)]);
+    let labels = CoffmanGraham::new(&graph, 2).labels();
+
+    assert_eq!(labels[&0], 1);
+    assert_eq!(labels[&1], 2);
+    assert_eq!(labels[&2], 3);
+    assert_eq!(labels[&3], 4);
+    assert_eq!(labels[&4], 5);
+    assert_eq!(
+      CoffmanGraham::new(&graph, 2).layering(),
+      Layering::from([(0, 0), (1, 0), (2, 1), (3, 1), (4, 2)])
+    );
+  }
+
+  #[test]
+  fn is_valid_on_larger_dag() {
+    let graph = graph_with_multiple_cliques();
+    let mut dag = graph.clone();
+    GreedyHeuristic::new(&graph)
+      .feedback_arc_set()
+      .into_iter()
+      .for_each(|e| dag.remove_edge(e));
+
+    let layering = CoffmanGraham::new(&dag, 3).layering();
+    assert!(is_valid(&dag, &layering));
+    assert!(width(&layering) <= 3);
+    assert!(layering.values().max() >= LongestPath::new(&dag).layering().values().max());
+  }
+
+  #[test]
+  fn is_valid_on_h_001() {
+    let graph = graph_from_file("h_001");
+    let mut dag = graph.clone();
+    GreedyHeuristic::new(&graph)
+      .feedback_arc_set()
+      .into_iter()
+      .for_each(|e| dag.remove_edge(e));
+
+    let layering = CoffmanGraham::new(&dag, 16).layering();
+    assert!(is_valid(&dag, &layering));
+    assert!(width(&layering) <= 16);
+  }
+}
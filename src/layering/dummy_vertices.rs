@@ -0,0 +1,116 @@
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use crate::layering::layer_assignment::Layering;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/*
+Makes a layering proper, i.e. every arc spans exactly one layer, as required by the crossing
+minimization step of the Sugiyama framework:
+
+d := max vertex id + 1
+for every arc u → v spanning k = layer(v) - layer(u) > 1 layers
+    replace u → v by the chain u → d → d + 1 → ... → d + k - 2 → v
+    d := d + k - 1                   {the dummy vertex d + i is on layer layer(u) + i + 1}
+ */
+#[derive(Clone, Debug)]
+pub struct ProperLayering {
+  pub graph: HashTable,
+  pub layering: Layering,
+  /// The dummy vertices of every arc that spans more than one layer, from top to bottom
+  pub chains: BTreeMap<Edge, Vec<VertexId>>,
+  /// The ids of all dummy vertices, they follow the largest id of the graph
+  pub dummies: Range<VertexId>,
+}
+
+impl ProperLayering {
+  /// Panics if an arc does not point to a lower layer, i.e. the layering is not valid for the graph
+  pub fn new(graph: &HashTable, layering: &Layering) -> Self {
+    let first_dummy = graph.vertices().last().map_or(0, |v| v + 1);
+    let mut next_dummy = first_dummy;
+    let mut layering = layering.clone();
+    let mut chains = BTreeMap::new();
+    let mut edges = vec![];
+
+    for (u, v) in graph.all_edges() {
+      assert!(
+        layering[&v] > layering[&u],
+        "Arc {u} → {v} does not point downwards: layer {} → {}",
+        layering[&u],
+        layering[&v]
+      );
+      let span = layering[&v] - layering[&u];
+      if span <= 1 {
+        edges.push((u, v));
+        continue;
+      }
+
+      let chain = (next_dummy..next_dummy + span as VertexId - 1).collect::<Vec<_>>();
+      next_dummy += span as VertexId - 1;
+      let mut previous = u;
+      for (i, dummy) in chain.iter().enumerate() {
+        layering.insert(*dummy, layering[&u] + i + 1);
+        edges.push((previous, *dummy));
+        previous = *dummy;
+      }
+      edges.push((previous, v));
+      chains.insert((u, v), chain);
+    }
+
+    let vertices = layering.keys().copied().collect::<Vec<_>>();
+    Self {
+      graph: HashTable::from_vertices_and_edges(&vertices, &edges),
+      layering,
+      chains,
+      dummies: first_dummy..next_dummy,
+    }
+  }
+
+  pub fn is_dummy(&self, v: VertexId) -> bool {
+    self.dummies.contains(&v)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::graph::hash_table::HashTable;
+  use crate::layering::dummy_vertices::ProperLayering;
+  use crate::layering::layer_assignment::{is_valid, total_span, LayerAssignment, Layering};
+  use crate::layering::longest_path::LongestPath;
+
+  #[test]
+  fn inserts_chains_of_dummy_vertices() {
+    let graph = HashTable::from_edges(&[(0, 1), (1, 2), (0, 2), (2, 3), (0, 3)]);
+    let layering = Layering::from([(0, 0), (1, 1), (2, 2), (3, 3)]);
+    let proper = ProperLayering::new(&graph, &layering);
+
+    assert_eq!(proper.chains[&(0, 2)], vec![4]);
+    assert_eq!(proper.chains[&(0, 3)], vec![5, 6]);
+    assert_eq!(proper.layering[&6], 2);
+    assert_eq!(proper.dummies, 4..7);
+    assert!(proper.is_dummy(5) && !proper.is_dummy(3));
+    assert!(proper.graph.has_edge(0, 5) && proper.graph.has_edge(6, 3));
+    assert!(!proper.graph.has_edge(0, 3));
+  }
+
+  #[test]
+  fn every_arc_spans_one_layer() {
+    let graph = HashTable::from_edges(&[(0, 1), (1, 2), (2, 3), (4, 3), (0, 3)]);
+    let layering = LongestPath::new(&graph).layering();
+    let proper = ProperLayering::new(&graph, &layering);
+
+    assert!(is_valid(&proper.graph, &proper.layering));
+    assert_eq!(
+      total_span(&proper.graph, &proper.layering),
+      proper.graph.edge_count()
+    );
+    assert_eq!(proper.graph.edge_count(), total_span(&graph, &layering));
+  }
+
+  #[test]
+  #[should_panic(expected = "does not point downwards")]
+  fn rejects_upward_arcs() {
+    let graph = HashTable::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    let layering = Layering::from([(0, 0), (1, 1), (2, 2)]);
+    ProperLayering::new(&graph, &layering);
+  }
+}
@@ -0,0 +1,55 @@
+use crate::graph::hash_table::{HashTable, VertexId};
+use std::collections::BTreeMap;
+
+/// Layer of every vertex, layer 0 is the top. Every arc points from a lower to a higher layer.
+pub type Layering = BTreeMap<VertexId, usize>;
+
+/// Second step of the Sugiyama framework: assigns the vertices of a DAG (e.g. after removing or
+/// reversing a feedback arc set) to layers
+pub trait LayerAssignment {
+  fn layering(&self) -> Layering;
+}
+
+/// Whether every vertex has a layer and every arc points downwards
+pub fn is_valid(graph: &HashTable, layering: &Layering) -> bool {
+  graph.vertices().iter().all(|v| layering.contains_key(v))
+    && graph
+      .all_edges()
+      .iter()
+      .all(|(u, v)| layering[u] < layering[v])
+}
+
+/// Sum of the number of layers every arc spans
+pub fn total_span(graph: &HashTable, layering: &Layering) -> usize {
+  graph
+    .all_edges()
+    .iter()
+    .map(|(u, v)| layering[v] - layering[u])
+    .sum()
+}
+
+/// Number of vertices in the fullest layer
+pub fn width(layering: &Layering) -> usize {
+  let mut vertices_per_layer = BTreeMap::<usize, usize>::new();
+  for layer in layering.values() {
+    *vertices_per_layer.entry(*layer).or_default() += 1;
+  }
+  vertices_per_layer.into_values().max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::graph::hash_table::HashTable;
+  use crate::layering::layer_assignment::{is_valid, total_span, width, Layering};
+
+  #[test]
+  fn measures_layering() {
+    let graph = HashTable::from_edges(&[(0, 1), (0, 2), (1, 2)]);
+    let layering = Layering::from([(0, 0), (1, 1), (2, 3)]);
+
+    assert!(is_valid(&graph, &layering));
+    assert_eq!(total_span(&graph, &layering), 6);
+    assert_eq!(width(&layering), 1);
+    assert!(!is_valid(&graph, &Layering::from([(0, 0), (1, 1), (2, 1)])));
+  }
+}
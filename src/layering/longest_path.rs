@@ -0,0 +1,59 @@
+use crate::graph::hash_table::HashTable;
+use crate::layering::layer_assignment::{LayerAssignment, Layering};
+use crate::ordering::topological_sort::TopologicalSort;
+
+/*
+Longest-path layering: Sources are placed on layer 0 and every other vertex one layer below its
+lowest predecessor, i.e. on the length of the longest path ending in it. Uses the minimum number of
+layers in O(|V| + |E|), but layers can get wide and arcs long.
+ */
+pub struct LongestPath<'a> {
+  pub graph: &'a HashTable,
+}
+
+impl<'a> LongestPath<'a> {
+  pub fn new(graph: &'a HashTable) -> Self {
+    Self { graph }
+  }
+}
+
+impl<'a> LayerAssignment for LongestPath<'a> {
+  fn layering(&self) -> Layering {
+    let sorted = TopologicalSort::new(self.graph)
+      .sort()
+      .expect("Layer assignment needs an acyclic graph");
+
+    let mut layering = Layering::new();
+    for u in sorted {
+      let layer = *layering.entry(u).or_insert(0);
+      for v in self.graph.neighborhood(&u) {
+        let layer_of_v = layering.entry(*v).or_insert(0);
+        *layer_of_v = (*layer_of_v).max(layer + 1);
+      }
+    }
+    layering
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::graph::hash_table::HashTable;
+  use crate::layering::layer_assignment::{is_valid, LayerAssignment, Layering};
+  use crate::layering::longest_path::LongestPath;
+
+  #[test]
+  fn places_vertices_below_longest_path() {
+    let graph = HashTable::from_edges(&[(0, 1), (1, 2), (0, 2), (3, 2)]);
+    let layering = LongestPath::new(&graph).layering();
+
+    assert_eq!(layering, Layering::from([(0, 0), (1, 1), (2, 2), (3, 0)]));
+    assert!(is_valid(&graph, &layering));
+  }
+
+  #[test]
+  #[should_panic]
+  fn rejects_cyclic_graph() {
+    let graph = HashTable::from_edges(&[(0, 1), (1, 0)]);
+    LongestPath::new(&graph).layering();
+  }
+}
@@ -0,0 +1,5 @@
+pub mod coffman_graham;
+pub mod dummy_vertices;
+pub mod layer_assignment;
+pub mod longest_path;
+pub mod network_simplex;
@@ -0,0 +1,393 @@
+/*
+Network simplex layering by Gansner, Koutsofios, North and Vo (A technique for drawing directed
+graphs, 1993). Minimizes the total weighted span Σ w(u → v) (layer(v) - layer(u)) subject to
+layer(v) - layer(u) ≥ 1 for every arc:
+
+init_rank();                        {longest-path layering}
+feasible_tree();                    {spanning tree of tight arcs, i.e. slack = span - 1 = 0}
+while (e = leave_edge()) != nil     {tree arc with negative cut value}
+    f = enter_edge(e);              {non-tree arc from the head to the tail component of minimal slack}
+    exchange(e, f);                 {f replaces e, layers are recomputed along the tree}
+normalize();                        {the top layer becomes 0}
+
+The cut value of a tree arc is the weight of all arcs from its tail component to its head component
+minus the weight of all arcs in the opposite direction. Every weakly connected component is solved
+on its own. Cut values are recomputed from scratch, so an iteration costs O(|V| (|V| + |E|)).
+ */
+
+use crate::graph::hash_table::{Edge, HashTable};
+use crate::layering::layer_assignment::{LayerAssignment, Layering};
+use crate::layering::longest_path::LongestPath;
+use std::collections::{BTreeMap, HashMap};
+
+pub struct NetworkSimplex<'a> {
+  pub graph: &'a HashTable,
+  /// Weight of the arcs, arcs without a weight weigh 1
+  pub weights: BTreeMap<Edge, u64>,
+  /// Upper bound for the number of exchanges, in case degenerate exchanges cycle
+  pub max_iterations: usize,
+}
+
+impl<'a> NetworkSimplex<'a> {
+  pub fn new(graph: &'a HashTable) -> Self {
+    Self::with_weights(graph, BTreeMap::new())
+  }
+
+  /// E.g. the weights of a Reorientation, so that merged arcs are kept short
+  pub fn with_weights(graph: &'a HashTable, weights: BTreeMap<Edge, u64>) -> Self {
+    Self {
+      graph,
+      weights,
+      max_iterations: 10_000,
+    }
+  }
+}
+
+impl<'a> LayerAssignment for NetworkSimplex<'a> {
+  fn layering(&self) -> Layering {
+    let initial = LongestPath::new(self.graph).layering();
+    let vertices = self.graph.vertices();
+    let index = vertices
+      .iter()
+      .enumerate()
+      .map(|(i, v)| (*v, i))
+      .collect::<HashMap<_, _>>();
+
+    let mut components = Components::new(vertices.len());
+    let arcs = self
+      .graph
+      .all_edges()
+      .into_iter()
+      .map(|(u, v)| {
+        let weight = self.weights.get(&(u, v)).copied().unwrap_or(1) as i64;
+        components.union(index[&u], index[&v]);
+        (index[&u], index[&v], weight)
+      })
+      .collect::<Vec<_>>();
+
+    let mut layering = Layering::new();
+    for component in components.into_sets() {
+      let local = component
+        .iter()
+        .enumerate()
+        .map(|(local, i)| (*i, local))
+        .collect::<HashMap<_, _>>();
+      let local_arcs = arcs
+        .iter()
+        .filter(|(u, _, _)| local.contains_key(u))
+        .map(|(u, v, weight)| (local[u], local[v], *weight))
+        .collect();
+      let rank = component
+        .iter()
+        .map(|i| initial[&vertices[*i]] as i64)
+        .collect();
+
+      let mut simplex = Simplex::new(rank, local_arcs);
+      simplex.solve(self.max_iterations);
+
+      let top = simplex.rank.iter().min().copied().unwrap_or(0);
+      for (i, rank) in component.iter().zip(simplex.rank) {
+        layering.insert(vertices[*i], (rank - top) as usize);
+      }
+    }
+    layering
+  }
+}
+
+struct Simplex {
+  rank: Vec<i64>,
+  arcs: Vec<(usize, usize, i64)>,
+  // Arcs incident to each vertex
+  incident: Vec<Vec<usize>>,
+  in_tree: Vec<bool>,
+}
+
+impl Simplex {
+  fn new(rank: Vec<i64>, arcs: Vec<(usize, usize, i64)>) -> Self {
+    let mut incident = vec![vec![]; rank.len()];
+    for (a, (u, v, _)) in arcs.iter().enumerate() {
+      incident[*u].push(a);
+      incident[*v].push(a);
+    }
+    Self {
+      in_tree: vec![false; arcs.len()],
+      rank,
+      arcs,
+      incident,
+    }
+  }
+
+  fn slack(&self, a: usize) -> i64 {
+    let (u, v, _) = self.arcs[a];
+    self.rank[v] - self.rank[u] - 1
+  }
+
+  fn other_end(&self, a: usize, x: usize) -> usize {
+    let (u, v, _) = self.arcs[a];
+    if u == x {
+      v
+    } else {
+      u
+    }
+  }
+
+  fn solve(&mut self, max_iterations: usize) {
+    self.feasible_tree();
+
+    for _ in 0..max_iterations {
+      let Some((e, tail_side)) = self.leave_edge() else {
+        break;
+      };
+      let f = (0..self.arcs.len())
+        .filter(|a| {
+          let (u, v, _) = self.arcs[*a];
+          !self.in_tree[*a] && !tail_side[u] && tail_side[v]
+        })
+        .min_by_key(|a| self.slack(*a))
+        .expect("A negative cut value needs an arc from the head to the tail component");
+
+      self.in_tree[e] = false;
+      self.in_tree[f] = true;
+      self.rank_along_tree();
+      debug_assert!((0..self.arcs.len()).all(|a| self.slack(a) >= 0));
+    }
+  }
+
+  // Grows a tree of tight arcs, shifting the tree until it spans all vertices
+  fn feasible_tree(&mut self) {
+    loop {
+      let tree_vertices = self.tight_tree();
+      if tree_vertices.iter().all(|in_tree| *in_tree) {
+        return;
+      }
+
+      let a = (0..self.arcs.len())
+        .filter(|a| {
+          let (u, v, _) = self.arcs[*a];
+          tree_vertices[u] != tree_vertices[v]
+        })
+        .min_by_key(|a| self.slack(*a))
+        .unwrap();
+      let (u, _, _) = self.arcs[a];
+      let delta = if tree_vertices[u] {
+        self.slack(a)
+      } else {
+        -self.slack(a)
+      };
+      for (x, in_tree) in tree_vertices.iter().enumerate() {
+        if *in_tree {
+          self.rank[x] += delta;
+        }
+      }
+    }
+  }
+
+  // Returns the vertices of a maximal tree of tight arcs containing vertex 0
+  fn tight_tree(&mut self) -> Vec<bool> {
+    self.in_tree.iter_mut().for_each(|a| *a = false);
+    let mut tree_vertices = vec![false; self.rank.len()];
+    if self.rank.is_empty() {
+      return tree_vertices;
+    }
+
+    tree_vertices[0] = true;
+    let mut stack = vec![0];
+    while let Some(x) = stack.pop() {
+      for a in self.incident[x].clone() {
+        let y = self.other_end(a, x);
+        if !tree_vertices[y] && self.slack(a) == 0 {
+          tree_vertices[y] = true;
+          self.in_tree[a] = true;
+          stack.push(y);
+        }
+      }
+    }
+    tree_vertices
+  }
+
+  // Vertices on the side of the tail of tree arc e, if e is removed from the tree
+  fn tail_component(&self, e: usize) -> Vec<bool> {
+    let (u, _, _) = self.arcs[e];
+    let mut tail_side = vec![false; self.rank.len()];
+    tail_side[u] = true;
+    let mut stack = vec![u];
+    while let Some(x) = stack.pop() {
+      for &a in &self.incident[x] {
+        let y = self.other_end(a, x);
+        if a != e && self.in_tree[a] && !tail_side[y] {
+          tail_side[y] = true;
+          stack.push(y);
+        }
+      }
+    }
+    tail_side
+  }
+
+  fn cut_value(&self, tail_side: &[bool]) -> i64 {
+    self
+      .arcs
+      .iter()
+      .map(|(u, v, weight)| match (tail_side[*u], tail_side[*v]) {
+        (true, false) => *weight,
+        (false, true) => -*weight,
+        _ => 0,
+      })
+      .sum()
+  }
+
+  fn leave_edge(&self) -> Option<(usize, Vec<bool>)> {
+    (0..self.arcs.len())
+      .filter(|e| self.in_tree[*e])
+      .map(|e| (e, self.tail_component(e)))
+      .find(|(_, tail_side)| self.cut_value(tail_side) < 0)
+  }
+
+  // All tree arcs are tight, so the ranks follow from the rank of vertex 0
+  fn rank_along_tree(&mut self) {
+    let mut visited = vec![false; self.rank.len()];
+    visited[0] = true;
+    let mut stack = vec![0];
+    while let Some(x) = stack.pop() {
+      for a in self.incident[x].clone() {
+        let (u, v, _) = self.arcs[a];
+        let y = self.other_end(a, x);
+        if self.in_tree[a] && !visited[y] {
+          self.rank[y] = if y == v {
+            self.rank[u] + 1
+          } else {
+            self.rank[v] - 1
+          };
+          visited[y] = true;
+          stack.push(y);
+        }
+      }
+    }
+  }
+}
+
+// Union-find on vertex indices for the weakly connected components
+struct Components {
+  parent: Vec<usize>,
+}
+
+impl Components {
+  fn new(n: usize) -> Self {
+    Self {
+      parent: (0..n).collect(),
+    }
+  }
+
+  fn find(&mut self, x: usize) -> usize {
+    let mut root = x;
+    while self.parent[root] != root {
+      root = self.parent[root];
+    }
+    self.parent[x] = root;
+    root
+  }
+
+  fn union(&mut self, x: usize, y: usize) {
+    let (x, y) = (self.find(x), self.find(y));
+    self.parent[x] = y;
+  }
+
+  // The components, each sorted by vertex index
+  fn into_sets(mut self) -> Vec<Vec<usize>> {
+    let mut sets = BTreeMap::<usize, Vec<usize>>::new();
+    for x in 0..self.parent.len() {
+      let root = self.find(x);
+      sets.entry(root).or_default().push(x);
+    }
+    sets.into_values().collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::graph::hash_table::{Edge, HashTable};
+  use crate::layering::layer_assignment::{is_valid, total_span, LayerAssignment, Layering};
+  use crate::layering::longest_path::LongestPath;
+  use crate::layering::network_simplex::NetworkSimplex;
+  use rand::rngs::StdRng;
+  use rand::{Rng, SeedableRng};
+  use std::collections::BTreeMap;
+
+  fn weighted_span(graph: &HashTable, weights: &BTreeMap<Edge, u64>, layering: &Layering) -> u64 {
+    graph
+      .all_edges()
+      .iter()
+      .map(|e| weights.get(e).unwrap_or(&1) * (layering[&e.1] - layering[&e.0]) as u64)
+      .sum()
+  }
+
+  // Minimum weighted span by trying all layerings with layers 0..n
+  fn optimal_span(graph: &HashTable, weights: &BTreeMap<Edge, u64>) -> u64 {
+    let vertices = graph.vertices();
+    let n = vertices.len();
+    (0..n.pow(n as u32))
+      .map(|mut code| {
+        vertices
+          .iter()
+          .map(|v| {
+            let layer = code % n;
+            code /= n;
+            (*v, layer)
+          })
+          .collect::<Layering>()
+      })
+      .filter(|layering| is_valid(graph, layering))
+      .map(|layering| weighted_span(graph, weights, &layering))
+      .min()
+      .unwrap()
+  }
+
+  #[test]
+  fn shortens_arcs_of_longest_path() {
+    // Longest path puts the source 4 on top, although it only points to the bottom vertex 3
+    let graph = HashTable::from_edges(&[(0, 1), (1, 2), (2, 3), (4, 3)]);
+    let layering = NetworkSimplex::new(&graph).layering();
+
+    assert_eq!(total_span(&graph, &LongestPath::new(&graph).layering()), 6);
+    assert_eq!(total_span(&graph, &layering), 4);
+    assert_eq!(layering[&4], 2);
+  }
+
+  #[test]
+  fn respects_weights() {
+    // The heavy arc 0 → 3 pulls 3 up, so that the path 1 → 2 → 3 is placed above 0
+    let graph = HashTable::from_edges(&[(1, 2), (2, 3), (0, 3)]);
+    let weights = BTreeMap::from([((0, 3), 5)]);
+    let layering = NetworkSimplex::with_weights(&graph, weights.clone()).layering();
+
+    assert!(is_valid(&graph, &layering));
+    assert_eq!(layering[&3] - layering[&0], 1);
+    assert_eq!(weighted_span(&graph, &weights, &layering), 7);
+  }
+
+  #[test]
+  fn is_optimal_on_small_random_dags() {
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..30 {
+      // Arcs only point to larger ids, so the graph is acyclic
+      let mut edges = vec![];
+      let mut weights = BTreeMap::new();
+      for u in 0..5 {
+        for v in (u + 1)..5 {
+          if rng.gen_bool(0.4) {
+            edges.push((u, v));
+            weights.insert((u, v), rng.gen_range(1..4));
+          }
+        }
+      }
+      let graph = HashTable::from_vertices_and_edges(&[0, 1, 2, 3, 4], &edges);
+      let layering = NetworkSimplex::with_weights(&graph, weights.clone()).layering();
+
+      assert!(is_valid(&graph, &layering));
+      assert_eq!(
+        weighted_span(&graph, &weights, &layering),
+        optimal_span(&graph, &weights),
+        "{edges:?}"
+      );
+    }
+  }
+}
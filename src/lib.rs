@@ -2,7 +2,10 @@
 
 pub mod bisection;
 pub mod fas;
+pub mod generator;
 pub mod graph;
+pub mod layering;
 pub mod ordering;
 pub mod scc;
+pub mod solver;
 pub mod tools;
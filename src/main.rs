@@ -0,0 +1,67 @@
+mod cli;
+
+use cli::arguments::Arguments;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: fas <command> [options] [input]
+
+Commands:
+  solve   Computes a feedback arc set of a graph in METIS format (PACE 2022) read from input or stdin.
+          Prints the best solution found on SIGTERM, after the time limit or when the algorithm is done.
+          --algorithm <name>    greedy (default), simple, multilevel, divide_and_conquer_by_order,
+                                divide_and_conquer_by_bisection[_fm|_kl|_multilevel], sort_fas,
+                                chanas_kobylanski, bubble_sort, kwik_sort, page_rank, spectral,
+                                simulated_annealing or tabu_search
+          --time-limit <secs>   optional wall-clock limit
+          --seed <n>            seed of the first run of randomized algorithms (default: 0)
+          --dfvs                print a feedback vertex set instead of arcs. The PACE 2022 track
+                                expects this format, without it the arcs are printed
+          --summary             verify the solution and print algorithm, size, lower bound and
+                                wall time to stderr
+  portfolio  Runs several algorithms in parallel on every strongly connected component and keeps
+          the smallest FAS per component. Prints the FAS and which algorithm won where to stderr.
+          --algorithms <a,b>    comma separated algorithm names (default: greedy,page_rank,simulated_annealing)
+          --time-limit <secs>   no algorithm run is started after the limit
+          --threads <n>         worker threads (default: available cores)
+          --seed <n>            seed of randomized algorithms (default: 0)
+  stats   Prints vertex and arc counts, degree distributions, self-loops, 2-cycles and SCC sizes.
+          --json                print JSON instead of text
+  quality Runs the algorithms on the bundled h_*/e_* instances and compares the FAS sizes.
+          --algorithms <a,b>    comma separated algorithm names (default: all)
+          --seeds <n>           runs of randomized algorithms with seeds 0..n (default: 5)
+          --dir <directory>     use the METIS files of this directory instead
+          --manifest <file>     best known FAS sizes (default: test/resources/manifest.csv)
+          --output <prefix>     writes <prefix>.csv and <prefix>.md (default: target/quality)
+  generate <family>  Prints a generated graph: random, tournament, scale-free, grid, layered,
+          de-bruijn, kautz or planted.
+          --n <n>               number of vertices (default: 100)
+          --density <p>         arc probability of random, layered and planted
+          --noise <p>           reversed arcs of tournament (near-transitive) and layered
+          --degree <d>          arcs per vertex of scale-free, alphabet of de-bruijn and kautz
+          --rows, --columns     size of grid; --layers, --width of layered; --length of words
+          --planted <k>         size of the planted FAS (default: n / 10)
+          --seed <n>            seed of the random generators (default: 0)
+          --format <name>       metis (default) or dot";
+
+fn main() -> ExitCode {
+  let mut args = std::env::args().skip(1);
+  let command = args.next();
+  let arguments = Arguments::new(args.collect());
+
+  let result = match command.as_deref() {
+    Some("solve") => cli::solve::solve(arguments),
+    Some("portfolio") => cli::portfolio::portfolio(arguments),
+    Some("stats") => cli::stats::stats(arguments),
+    Some("quality") => cli::quality::quality(arguments),
+    Some("generate") => cli::generate::generate(arguments),
+    _ => Err(USAGE.to_string()),
+  };
+
+  match result {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(message) => {
+      eprintln!("{message}");
+      ExitCode::FAILURE
+    }
+  }
+}
@@ -0,0 +1,257 @@
+/*
+A vertex ordering of a WeightedDigraph with the weight of its leftward arcs (cost), as state of the
+local searches over orderings. Both moves are evaluated in O(deg), because only the arcs between the
+moved vertices and the vertices they pass change their direction:
+
+insertion of v from position i to j > i    Δ = w(v → passed vertices) - w(passed vertices → v)
+insertion of v from position i to j < i    Δ = w(passed vertices → v) - w(v → passed vertices)
+swap of u at position i and v at j > i     as inserting u at j - 1 and v at i, plus w(u → v) - w(v → u)
+
+The best ordering seen so far is only copied before a move leaves it for a worse one.
+ */
+
+use crate::bisection::weighted_digraph::WeightedDigraph;
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use std::collections::BTreeMap;
+
+pub(crate) struct Arrangement<'g> {
+  graph: &'g WeightedDigraph,
+  pub ordering: Vec<usize>,
+  pub position: Vec<usize>,
+  pub cost: i64,
+  best: Vec<usize>,
+  pub best_cost: i64,
+  // Whether best holds the best ordering, otherwise the current ordering is the best one
+  best_saved: bool,
+}
+
+impl<'g> Arrangement<'g> {
+  pub(crate) fn new(graph: &'g WeightedDigraph, ordering: Vec<usize>) -> Self {
+    let mut position = vec![0; ordering.len()];
+    ordering
+      .iter()
+      .enumerate()
+      .for_each(|(p, v)| position[*v] = p);
+    let cost = (0..graph.order())
+      .flat_map(|u| graph.successors[u].iter().map(move |(v, w)| (u, *v, *w)))
+      .filter(|(u, v, _)| position[*v] < position[*u])
+      .map(|(_, _, w)| w)
+      .sum();
+
+    Self {
+      graph,
+      ordering,
+      position,
+      cost,
+      best: vec![],
+      best_cost: cost,
+      best_saved: false,
+    }
+  }
+
+  pub(crate) fn insertion_delta(&self, v: usize, to: usize) -> i64 {
+    let from = self.position[v];
+    let passed = |x: &usize| {
+      let p = self.position[*x];
+      (from < p && p <= to) || (to <= p && p < from)
+    };
+    let outgoing = self.weight_to(&self.graph.successors[v], passed);
+    let incoming = self.weight_to(&self.graph.predecessors[v], passed);
+    if to > from {
+      outgoing - incoming
+    } else {
+      incoming - outgoing
+    }
+  }
+
+  /// Swaps the vertices at positions i < j
+  pub(crate) fn swap_delta(&self, i: usize, j: usize) -> i64 {
+    let (u, v) = (self.ordering[i], self.ordering[j]);
+    let between = |x: &usize| i < self.position[*x] && self.position[*x] < j;
+    let pair = self.weight_to(&self.graph.successors[u], |x| *x == v)
+      - self.weight_to(&self.graph.successors[v], |x| *x == u);
+
+    self.weight_to(&self.graph.successors[u], between)
+      - self.weight_to(&self.graph.predecessors[u], between)
+      + self.weight_to(&self.graph.predecessors[v], between)
+      - self.weight_to(&self.graph.successors[v], between)
+      + pair
+  }
+
+  fn weight_to<F: Fn(&usize) -> bool>(&self, arcs: &[(usize, i64)], filter: F) -> i64 {
+    arcs.iter().filter(|(x, _)| filter(x)).map(|(_, w)| w).sum()
+  }
+
+  /// The insertion of v next to one of its neighbors with the smallest delta, even if it is positive.
+  /// Other targets do not change the cost compared to the closest neighbor on the way.
+  /// Without neighbors, v stays where it is.
+  pub(crate) fn best_insertion(&self, v: usize) -> (usize, i64) {
+    let from = self.position[v];
+    // (position of neighbor, delta if v passes it moving right)
+    let mut neighbors = self.graph.successors[v]
+      .iter()
+      .map(|(x, w)| (self.position[*x], *w))
+      .chain(
+        self.graph.predecessors[v]
+          .iter()
+          .map(|(x, w)| (self.position[*x], -*w)),
+      )
+      .collect::<Vec<_>>();
+    neighbors.sort_unstable();
+
+    let mut best: Option<(usize, i64)> = None;
+    let split = neighbors.partition_point(|(p, _)| *p < from);
+    let mut delta = 0;
+    for (k, (p, change)) in neighbors[split..].iter().enumerate() {
+      delta += change;
+      let last_at_p = neighbors.get(split + k + 1).is_none_or(|(q, _)| q != p);
+      if last_at_p && best.is_none_or(|(_, d)| delta < d) {
+        best = Some((*p, delta));
+      }
+    }
+    delta = 0;
+    for (k, (p, change)) in neighbors[..split].iter().enumerate().rev() {
+      delta -= change;
+      let last_at_p = k == 0 || neighbors[k - 1].0 != *p;
+      if last_at_p && best.is_none_or(|(_, d)| delta < d) {
+        best = Some((*p, delta));
+      }
+    }
+    best.unwrap_or((from, 0))
+  }
+
+  pub(crate) fn insert(&mut self, v: usize, to: usize, delta: i64) {
+    self.before_move(delta);
+    let from = self.position[v];
+    if from < to {
+      self.ordering[from..=to].rotate_left(1);
+    } else {
+      self.ordering[to..=from].rotate_right(1);
+    }
+    for p in from.min(to)..=from.max(to) {
+      self.position[self.ordering[p]] = p;
+    }
+    self.after_move(delta);
+  }
+
+  pub(crate) fn swap(&mut self, i: usize, j: usize, delta: i64) {
+    self.before_move(delta);
+    self.ordering.swap(i, j);
+    self.position[self.ordering[i]] = i;
+    self.position[self.ordering[j]] = j;
+    self.after_move(delta);
+  }
+
+  fn before_move(&mut self, delta: i64) {
+    if delta > 0 && !self.best_saved && self.cost == self.best_cost {
+      self.best.clone_from(&self.ordering);
+      self.best_saved = true;
+    }
+  }
+
+  fn after_move(&mut self, delta: i64) {
+    self.cost += delta;
+    if self.cost < self.best_cost {
+      self.best_cost = self.cost;
+      self.best_saved = false;
+    }
+  }
+
+  /// The ordering with the least cost seen
+  pub(crate) fn into_best(self) -> Vec<usize> {
+    if self.best_saved {
+      self.best
+    } else {
+      self.ordering
+    }
+  }
+}
+
+/// The digraph of the graph with the given arc weights (missing ones weigh 1) and the vertex of
+/// every index. Self-loops are left out, as they are leftward in every ordering.
+pub(crate) fn weighted_digraph(
+  graph: &HashTable,
+  weights: &BTreeMap<Edge, u64>,
+) -> (WeightedDigraph, Vec<VertexId>) {
+  let vertices = graph.vertices();
+  let index = |v: &VertexId| vertices.binary_search(v).unwrap();
+  let mut digraph = WeightedDigraph::with_vertex_weights(vec![1; vertices.len()]);
+  for e @ (u, v) in graph.all_edges() {
+    if u != v {
+      let weight = weights.get(&e).copied().unwrap_or(1) as i64;
+      digraph.add_arc(index(&u), index(&v), weight);
+    }
+  }
+  (digraph, vertices)
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::ordering::arrangement::{weighted_digraph, Arrangement};
+  use crate::tools::graphs::graph_from_file;
+  use rand::rngs::StdRng;
+  use rand::{Rng, SeedableRng};
+  use std::collections::BTreeMap;
+
+  #[test]
+  fn deltas_match_recomputed_cost() {
+    let graph = graph_from_file("h_001");
+    let (digraph, _) = weighted_digraph(&graph, &BTreeMap::new());
+    let n = digraph.order();
+    let mut arrangement = Arrangement::new(&digraph, (0..n).collect());
+    let mut rng = StdRng::seed_from_u64(0);
+
+    for _ in 0..200 {
+      let (i, j) = (rng.gen_range(0..n), rng.gen_range(0..n));
+      let v = arrangement.ordering[i];
+      let delta = arrangement.insertion_delta(v, j);
+      arrangement.insert(v, j, delta);
+      assert_eq!(
+        arrangement.cost,
+        Arrangement::new(&digraph, arrangement.ordering.clone()).cost
+      );
+
+      let (i, j) = (i.min(j), i.max(j));
+      if i < j {
+        let delta = arrangement.swap_delta(i, j);
+        arrangement.swap(i, j, delta);
+        assert_eq!(
+          arrangement.cost,
+          Arrangement::new(&digraph, arrangement.ordering.clone()).cost
+        );
+      }
+
+      let v = arrangement.ordering[rng.gen_range(0..n)];
+      let (to, delta) = arrangement.best_insertion(v);
+      assert_eq!(delta, arrangement.insertion_delta(v, to));
+      let best_next_to_neighbor = digraph.successors[v]
+        .iter()
+        .chain(&digraph.predecessors[v])
+        .map(|(x, _)| arrangement.insertion_delta(v, arrangement.position[*x]))
+        .min();
+      assert_eq!(Some(delta), best_next_to_neighbor);
+    }
+  }
+
+  #[test]
+  fn keeps_best_ordering() {
+    let graph = graph_from_file("h_001");
+    let (digraph, _) = weighted_digraph(&graph, &BTreeMap::new());
+    let mut arrangement = Arrangement::new(&digraph, (0..digraph.order()).collect());
+    let initial = arrangement.cost;
+
+    let v = arrangement.ordering[0];
+    let delta = arrangement.insertion_delta(v, 500);
+    arrangement.insert(v, 500, delta);
+    let v = arrangement.ordering[10];
+    let delta = arrangement.insertion_delta(v, 900);
+    arrangement.insert(v, 900, delta);
+
+    let best_cost = arrangement.best_cost;
+    assert!(best_cost <= initial);
+    assert_eq!(
+      Arrangement::new(&digraph, arrangement.into_best()).cost,
+      best_cost
+    );
+  }
+}
@@ -0,0 +1,195 @@
+/*
+Dynamic topological order by Pearce and Kelly (A dynamic topological sort algorithm for directed
+acyclic graphs, 2006). Adding an arc u → v with ord(u) < ord(v) keeps the order. Otherwise only the
+vertices between ord(v) and ord(u) can be affected:
+
+add_edge(u, v)
+    if ord(u) > ord(v) then
+        δF := vertices reachable from v with ord ≤ ord(u)   {forward search, fails if u is reached}
+        δB := vertices reaching u with ord ≥ ord(v)         {backward search}
+        assign the positions of δB ∪ δF to δB followed by δF, both in their previous order
+    insert u → v
+
+Both searches only visit vertices of the affected region, which is usually small.
+ */
+
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use crate::ordering::topological_sort::TopologicalSort;
+use std::collections::HashMap;
+
+pub struct IncrementalTopologicalOrder {
+  vertices: Vec<VertexId>,
+  index: HashMap<VertexId, usize>,
+  successors: Vec<Vec<usize>>,
+  predecessors: Vec<Vec<usize>>,
+  /// Position of every vertex index in the topological order
+  ord: Vec<usize>,
+  // Reused by the searches, true for all vertices of δF or δB
+  visited: Vec<bool>,
+}
+
+impl IncrementalTopologicalOrder {
+  /// Returns None if the graph contains a cycle
+  pub fn new(graph: &HashTable) -> Option<Self> {
+    let sorted = TopologicalSort::new(graph).sort()?;
+    let vertices = graph.vertices();
+    let index = vertices
+      .iter()
+      .enumerate()
+      .map(|(i, v)| (*v, i))
+      .collect::<HashMap<_, _>>();
+
+    let mut ord = vec![0; vertices.len()];
+    for (position, v) in sorted.iter().enumerate() {
+      ord[index[v]] = position;
+    }
+    let mut successors = vec![vec![]; vertices.len()];
+    let mut predecessors = vec![vec![]; vertices.len()];
+    for (u, v) in graph.all_edges() {
+      successors[index[&u]].push(index[&v]);
+      predecessors[index[&v]].push(index[&u]);
+    }
+
+    Some(Self {
+      visited: vec![false; vertices.len()],
+      vertices,
+      index,
+      successors,
+      predecessors,
+      ord,
+    })
+  }
+
+  /// Inserts the arc and returns true, unless it would close a cycle
+  pub fn try_add_edge(&mut self, (u, v): Edge) -> bool {
+    let (u, v) = (self.index[&u], self.index[&v]);
+    let (lower_bound, upper_bound) = (self.ord[v], self.ord[u]);
+
+    if lower_bound <= upper_bound {
+      if u == v {
+        return false;
+      }
+      let Some(mut delta_f) = self.forward(v, upper_bound) else {
+        return false;
+      };
+      let mut delta_b = self.backward(u, lower_bound);
+      self.reorder(&mut delta_b, &mut delta_f);
+    }
+
+    self.successors[u].push(v);
+    self.predecessors[v].push(u);
+    true
+  }
+
+  /// The vertices in topological order
+  pub fn ordering(&self) -> Vec<VertexId> {
+    let mut ordering = vec![0; self.vertices.len()];
+    for (i, position) in self.ord.iter().enumerate() {
+      ordering[*position] = self.vertices[i];
+    }
+    ordering
+  }
+
+  // δF, or None if a vertex at upper_bound (i.e. u) is reachable from start
+  fn forward(&mut self, start: usize, upper_bound: usize) -> Option<Vec<usize>> {
+    let mut delta_f = vec![start];
+    let mut stack = vec![start];
+    self.visited[start] = true;
+
+    while let Some(w) = stack.pop() {
+      for &x in &self.successors[w] {
+        if self.ord[x] == upper_bound {
+          delta_f.iter().for_each(|i| self.visited[*i] = false);
+          return None;
+        }
+        if !self.visited[x] && self.ord[x] < upper_bound {
+          self.visited[x] = true;
+          delta_f.push(x);
+          stack.push(x);
+        }
+      }
+    }
+    Some(delta_f)
+  }
+
+  fn backward(&mut self, start: usize, lower_bound: usize) -> Vec<usize> {
+    let mut delta_b = vec![start];
+    let mut stack = vec![start];
+    self.visited[start] = true;
+
+    while let Some(w) = stack.pop() {
+      for &x in &self.predecessors[w] {
+        if !self.visited[x] && self.ord[x] > lower_bound {
+          self.visited[x] = true;
+          delta_b.push(x);
+          stack.push(x);
+        }
+      }
+    }
+    delta_b
+  }
+
+  fn reorder(&mut self, delta_b: &mut [usize], delta_f: &mut [usize]) {
+    delta_b.sort_unstable_by_key(|i| self.ord[*i]);
+    delta_f.sort_unstable_by_key(|i| self.ord[*i]);
+
+    let mut positions = delta_b
+      .iter()
+      .chain(delta_f.iter())
+      .map(|i| self.ord[*i])
+      .collect::<Vec<_>>();
+    positions.sort_unstable();
+
+    for (i, position) in delta_b.iter().chain(delta_f.iter()).zip(positions) {
+      self.ord[*i] = position;
+      self.visited[*i] = false;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::graph::hash_table::HashTable;
+  use crate::ordering::incremental_topological_order::IncrementalTopologicalOrder;
+  use crate::ordering::topological_sort::leftward_edges;
+  use rand::rngs::StdRng;
+  use rand::{Rng, SeedableRng};
+
+  #[test]
+  fn rejects_cyclic_graph() {
+    let clique = HashTable::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    assert!(IncrementalTopologicalOrder::new(&clique).is_none());
+  }
+
+  #[test]
+  fn reorders_affected_region() {
+    let mut order =
+      IncrementalTopologicalOrder::new(&HashTable::from_edges(&[(0, 1), (2, 3)])).unwrap();
+    assert_eq!(order.ordering(), vec![0, 2, 1, 3]);
+
+    assert!(order.try_add_edge((3, 0)));
+    assert_eq!(order.ordering(), vec![2, 3, 0, 1]);
+    assert!(!order.try_add_edge((1, 2)));
+    assert!(!order.try_add_edge((0, 0)));
+    assert!(order.try_add_edge((2, 1)));
+  }
+
+  #[test]
+  fn stays_topological_on_random_insertions() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut graph = HashTable::from_vertices_and_edges(&(0..30).collect::<Vec<_>>(), &[]);
+    let mut order = IncrementalTopologicalOrder::new(&graph).unwrap();
+
+    for _ in 0..300 {
+      let e = (rng.gen_range(0..30), rng.gen_range(0..30));
+      let mut with_e = graph.clone();
+      with_e.add_edge(e);
+
+      assert_eq!(order.try_add_edge(e), !with_e.is_cyclic(), "{e:?}");
+      if !with_e.is_cyclic() {
+        graph = with_e;
+      }
+      assert!(leftward_edges(&graph, order.ordering()).is_empty());
+    }
+  }
+}
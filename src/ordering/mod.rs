@@ -1 +1,4 @@
+pub(crate) mod arrangement;
+pub mod incremental_topological_order;
 pub mod topological_sort;
+pub mod vertex_ordering;
@@ -1,5 +1,5 @@
 use crate::graph::hash_table::{Direction, Edge, HashTable, VertexId};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 pub struct TopologicalSort<'a> {
   graph: &'a HashTable,
@@ -18,23 +18,67 @@ impl<'a> TopologicalSort<'a> {
       .map(|v| (*v, self.graph.edges(*v, Direction::Inbound).len()))
       .collect::<Vec<_>>();
 
-    vec.sort_by(|(_, v1_edge_count), (_, v2_edge_count)| v1_edge_count.cmp(v2_edge_count));
+    vec.sort_by_key(|(_, edge_count)| *edge_count);
     vec.iter().map(|(v, _)| *v).collect()
   }
+
+  // Kahn's algorithm. Returns None if the graph contains a cycle.
+  pub fn sort(&self) -> Option<Vec<VertexId>> {
+    let mut indegree = self
+      .graph
+      .vertices()
+      .into_iter()
+      .map(|v| (v, 0))
+      .collect::<HashMap<VertexId, usize>>();
+    for (_, destination) in self.graph.all_edges() {
+      *indegree.get_mut(&destination).unwrap() += 1;
+    }
+
+    let mut queue = self
+      .graph
+      .vertices()
+      .into_iter()
+      .filter(|v| indegree[v] == 0)
+      .collect::<VecDeque<_>>();
+    let mut sorted = Vec::with_capacity(indegree.len());
+
+    while let Some(v) = queue.pop_front() {
+      sorted.push(v);
+      for destination in self.graph.neighborhood(&v) {
+        let count = indegree.get_mut(destination).unwrap();
+        *count -= 1;
+        if *count == 0 {
+          queue.push_back(*destination);
+        }
+      }
+    }
+
+    if sorted.len() == indegree.len() {
+      Some(sorted)
+    } else {
+      None
+    }
+  }
 }
 
+/// Returns all arcs pointing backwards in the ordering, including self-loops. They form a feedback arc set.
+/// A self-loop is a cycle that no ordering can break, so it has to be part of the result. Otherwise
+/// every heuristic that returns the leftward arcs of its ordering would leave the self-loops of
+/// e.g. de Bruijn graphs in place.
 pub fn leftward_edges(graph: &HashTable, ordering: Vec<VertexId>) -> HashSet<Edge> {
   let mut leftward_edges = HashSet::new();
+  let positions = ordering
+    .iter()
+    .enumerate()
+    .map(|(idx, v)| (*v, idx))
+    .collect::<HashMap<_, _>>();
 
-  for source_idx in 0..ordering.len() {
-    let source = ordering[source_idx];
-
+  for (source_idx, &source) in ordering.iter().enumerate() {
     for (_, destination) in graph.edges(source, Direction::Outbound) {
-      let destination_idx = ordering
-        .iter()
-        .position(|v| *v == destination)
+      let destination_idx = *positions
+        .get(&destination)
         .unwrap_or_else(|| panic!("Ordering = {:?}, Destination = {:?}", ordering, destination));
-      if destination_idx < source_idx {
+      if destination_idx <= source_idx {
         leftward_edges.insert((source, destination));
       }
     }
@@ -46,11 +90,12 @@ pub fn leftward_edges(graph: &HashTable, ordering: Vec<VertexId>) -> HashSet<Edg
 #[cfg(test)]
 mod tests {
   use crate::graph::hash_table::{Direction, HashTable, VertexId};
-  use crate::ordering::topological_sort::TopologicalSort;
+  use crate::ordering::topological_sort::{leftward_edges, TopologicalSort};
   use crate::tools::graphs::{
     graph_from_file, graph_from_wikipedia_scc, graph_with_multiple_cliques,
     graph_with_simple_clique,
   };
+  use std::collections::HashSet;
 
   #[test]
   fn works_on_simple_clique() {
@@ -98,6 +143,50 @@ mod tests {
     assert_indegree_increasing(cyclic_graph, order);
   }
 
+  #[test]
+  fn sort_works_on_acyclic_graph() {
+    let graph = HashTable::from_edges(&[(3, 1), (1, 2), (3, 2), (2, 0)]);
+    let order = TopologicalSort::new(&graph).sort().unwrap();
+
+    assert_eq!(order, vec![3, 1, 2, 0]);
+  }
+
+  #[test]
+  fn sort_detects_cycles() {
+    let clique = graph_with_simple_clique();
+
+    assert_eq!(TopologicalSort::new(&clique).sort(), None);
+  }
+
+  #[test]
+  fn leftward_edges_of_ordering() {
+    let clique = graph_with_simple_clique();
+
+    assert_eq!(
+      leftward_edges(&clique, vec![0, 1, 2]),
+      HashSet::from([(2, 0)])
+    );
+    assert_eq!(
+      leftward_edges(&clique, vec![2, 1, 0]),
+      HashSet::from([(0, 1), (1, 2)])
+    );
+  }
+
+  #[test]
+  fn leftward_edges_include_self_loops() {
+    let mut graph = graph_with_simple_clique();
+    graph.add_edge((1, 1));
+
+    for ordering in [vec![0, 1, 2], vec![2, 1, 0]] {
+      let fas = leftward_edges(&graph, ordering);
+      let mut acyclic_graph = graph.clone();
+      fas.iter().for_each(|e| acyclic_graph.remove_edge(*e));
+
+      assert!(fas.contains(&(1, 1)));
+      assert!(!acyclic_graph.is_cyclic());
+    }
+  }
+
   fn assert_indegree_increasing(clique: HashTable, order: Vec<VertexId>) {
     let mut last_edge_count_in = usize::MIN;
     let mut last_vertex = 0;
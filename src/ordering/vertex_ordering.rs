@@ -0,0 +1,7 @@
+use crate::graph::hash_table::VertexId;
+
+/// Algorithms that arrange all vertices of a graph in a sequence, whose leftward arcs
+/// (see topological_sort::leftward_edges) form a feedback arc set
+pub trait VertexOrdering {
+  fn vertex_ordering(&self) -> Vec<VertexId>;
+}
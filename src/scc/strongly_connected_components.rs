@@ -1,9 +1,57 @@
-use crate::graph::hash_table::VertexId;
-use std::collections::HashSet;
+use crate::graph::hash_table::{HashTable, VertexId};
+use crate::scc::tarjan::Tarjan;
+use std::collections::{HashMap, HashSet};
 
 pub trait StronglyConnectedComponents {
   fn strongly_connected_components(&mut self) -> Vec<HashSet<VertexId>>;
 }
 
+/// The strongly connected components that contain a cycle, i.e. with more than one vertex or a
+/// self-loop, as induced subgraphs. Every cycle of the graph lies in one of them.
+pub fn cyclic_components(graph: &HashTable) -> Vec<HashTable> {
+  let sccs = Tarjan::new(graph).strongly_connected_components();
+  let component = sccs
+    .iter()
+    .enumerate()
+    .flat_map(|(i, scc)| scc.iter().map(move |v| (*v, i)))
+    .collect::<HashMap<_, _>>();
+
+  let mut arcs = vec![vec![]; sccs.len()];
+  for (u, v) in graph.all_edges() {
+    if component[&u] == component[&v] {
+      arcs[component[&u]].push((u, v));
+    }
+  }
+
+  sccs
+    .into_iter()
+    .zip(arcs)
+    .filter(|(scc, arcs)| scc.len() > 1 || !arcs.is_empty())
+    .map(|(scc, arcs)| {
+      let mut vertices = scc.into_iter().collect::<Vec<_>>();
+      vertices.sort_unstable();
+      HashTable::from_vertices_and_edges(&vertices, &arcs)
+    })
+    .collect()
+}
+
 #[cfg(test)]
-pub mod tests {}
+pub mod tests {
+  use crate::scc::strongly_connected_components::cyclic_components;
+  use crate::tools::graphs::graph_with_multiple_cliques;
+
+  #[test]
+  fn cyclic_components_keep_arcs_inside() {
+    let graph = graph_with_multiple_cliques();
+    let components = cyclic_components(&graph);
+
+    assert_eq!(components.len(), 1);
+    assert_eq!(components[0].order(), graph.order() - 3);
+    assert!(components[0].is_cyclic());
+
+    let self_loop = crate::graph::hash_table::HashTable::from_edges(&[(0, 0), (0, 1)]);
+    let components = cyclic_components(&self_loop);
+    assert_eq!(components.len(), 1);
+    assert_eq!(components[0].all_edges(), vec![(0, 0)]);
+  }
+}
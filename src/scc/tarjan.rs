@@ -50,7 +50,11 @@ impl Tarjan<'_> {
 
     let neighbors = self.graph.neighborhood(&self.vertices[v].id);
     for j in neighbors {
-      let w = self.vertices.iter().position(|i| i.id == *j).unwrap();
+      // The vertices are sorted by id (see initial_tarjan_nodes)
+      let w = self
+        .vertices
+        .binary_search_by_key(j, |vertex| vertex.id)
+        .unwrap();
       if self.vertices[w].index == UNDEFINED {
         self.scc(w);
         self.vertices[v].low_link = min(self.vertices[v].low_link, self.vertices[w].low_link);
@@ -120,7 +124,7 @@ mod tests {
 
     assert_eq!(sc_components.len(), 1);
     assert_eq!(
-      *sc_components.get(0).unwrap(),
+      *sc_components.first().unwrap(),
       HashSet::from_iter(clique.vertices())
     );
   }
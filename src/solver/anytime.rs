@@ -0,0 +1,166 @@
+use crate::fas::algorithm::Algorithm;
+use crate::fas::feedback_arc_set::FeedbackArcSet;
+use crate::fas::minimal::make_minimal;
+use crate::fas::tabu_search::{self, TabuSearch};
+use crate::graph::hash_table::{Edge, HashTable};
+use crate::ordering::topological_sort::{leftward_edges, TopologicalSort};
+use crate::solver::incumbent::Incumbent;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/*
+Runs a feedback arc set algorithm as an anytime process, as required by the PACE heuristic track:
+    seed := initial seed;
+    offer fas(G, seed) to the incumbent
+    repeat
+        seed := seed + 1
+        if the algorithm is randomized then offer fas(G, seed) to the incumbent   {restart}
+        s := topological order of G - incumbent
+        offer the FAS of tabu search from s with seed to the incumbent          {local search}
+    until stopped or, for deterministic algorithms, MAX_STALE_ROUNDS rounds did not improve the incumbent
+Every offered F is reduced to the arcs that can not be reinserted without closing a cycle (see make_minimal).
+The thread that owns the stop flag offers a trivial solution before the solver starts and prints the
+incumbent whenever it decides to stop.
+ */
+/// Number of local search rounds without improvement after which a deterministic algorithm stops
+pub const MAX_STALE_ROUNDS: usize = 5;
+
+pub struct AnytimeSolver<'a> {
+  graph: &'a HashTable,
+  algorithm: Algorithm,
+  seed: u64,
+  incumbent: Arc<Incumbent>,
+  stop: Arc<AtomicBool>,
+}
+
+impl<'a> AnytimeSolver<'a> {
+  pub fn new(
+    graph: &'a HashTable,
+    algorithm: Algorithm,
+    seed: u64,
+    incumbent: Arc<Incumbent>,
+    stop: Arc<AtomicBool>,
+  ) -> Self {
+    Self {
+      graph,
+      algorithm,
+      seed,
+      incumbent,
+      stop,
+    }
+  }
+
+  /// Improves the incumbent until the stop flag is set or, for deterministic algorithms, the local
+  /// search did not find a better solution MAX_STALE_ROUNDS times in a row
+  pub fn run(&self) {
+    if self.is_stopped() {
+      return;
+    }
+    let mut seed = self.seed;
+    self.offer(&self.algorithm.feedback_arc_set(self.graph, seed));
+
+    let mut stale_rounds = 0;
+    while !self.is_stopped() && (self.algorithm.is_randomized() || stale_rounds < MAX_STALE_ROUNDS)
+    {
+      seed = seed.wrapping_add(1);
+      let mut improved = false;
+      if self.algorithm.is_randomized() {
+        improved |= self.offer(&self.algorithm.feedback_arc_set(self.graph, seed));
+      }
+      if !self.is_stopped() {
+        improved |= self.offer(&self.local_search(seed));
+      }
+      stale_rounds = if improved { 0 } else { stale_rounds + 1 };
+    }
+  }
+
+  /// Tabu search starting from a topological order of the graph without the incumbent
+  fn local_search(&self, seed: u64) -> HashSet<Edge> {
+    let mut dag = self.graph.clone();
+    let incumbent = self.incumbent.best().unwrap();
+    incumbent.iter().for_each(|e| dag.remove_edge(*e));
+    let ordering = TopologicalSort::new(&dag)
+      .sort()
+      .expect("The incumbent is a feedback arc set");
+
+    let parameters = tabu_search::Parameters {
+      seed,
+      ..tabu_search::Parameters::default()
+    };
+    TabuSearch::with_parameters(self.graph, parameters)
+      .with_ordering(ordering)
+      .feedback_arc_set()
+  }
+
+  fn offer(&self, fas: &HashSet<Edge>) -> bool {
+    self.incumbent.offer(make_minimal(self.graph, fas))
+  }
+
+  fn is_stopped(&self) -> bool {
+    self.stop.load(Ordering::Relaxed)
+  }
+}
+
+/// The leftward arcs of the vertices ordered by id. Computed in linear time, so that there is
+/// a valid solution to print even if the time is up before the first algorithm run finished.
+pub fn trivial_feedback_arc_set(graph: &HashTable) -> HashSet<Edge> {
+  leftward_edges(graph, graph.vertices())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::tools::graphs::{graph_from_file, graph_from_wikipedia_scc};
+  use std::thread;
+  use std::time::Duration;
+
+  fn assert_is_feedback_arc_set(graph: &HashTable, fas: &HashSet<Edge>) {
+    let mut acyclic_graph = graph.clone();
+    fas.iter().for_each(|e| acyclic_graph.remove_edge(*e));
+    assert!(!acyclic_graph.is_cyclic());
+  }
+
+  #[test]
+  fn trivial_feedback_arc_set_is_valid() {
+    let graph = graph_from_file("h_001");
+    assert_is_feedback_arc_set(&graph, &trivial_feedback_arc_set(&graph));
+  }
+
+  #[test]
+  fn deterministic_algorithm_is_improved_and_terminates_without_stop() {
+    let graph = graph_from_file("h_001");
+    let incumbent = Arc::new(Incumbent::new());
+    let stop = Arc::new(AtomicBool::new(false));
+    let greedy = make_minimal(&graph, &Algorithm::Greedy.feedback_arc_set(&graph, 0));
+
+    AnytimeSolver::new(&graph, Algorithm::Greedy, 0, incumbent.clone(), stop).run();
+
+    assert_is_feedback_arc_set(&graph, &incumbent.best().unwrap());
+    assert!(incumbent.best_size().unwrap() < greedy.len());
+  }
+
+  #[test]
+  fn randomized_algorithm_stops_on_flag() {
+    let graph = graph_from_wikipedia_scc();
+    let incumbent = Arc::new(Incumbent::new());
+    let stop = Arc::new(AtomicBool::new(false));
+
+    thread::scope(|scope| {
+      scope.spawn(|| {
+        AnytimeSolver::new(
+          &graph,
+          Algorithm::Simple,
+          0,
+          incumbent.clone(),
+          stop.clone(),
+        )
+        .run()
+      });
+      thread::sleep(Duration::from_millis(50));
+      stop.store(true, Ordering::Relaxed);
+    });
+
+    assert_is_feedback_arc_set(&graph, &incumbent.best().unwrap());
+  }
+}
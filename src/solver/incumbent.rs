@@ -0,0 +1,62 @@
+use crate::graph::hash_table::Edge;
+use std::collections::HashSet;
+use std::sync::Mutex;
+
+/// The best feedback arc set found so far. It is shared between the thread running the
+/// algorithms, which offers new solutions, and the thread that has to print the best
+/// solution as soon as the time is up.
+#[derive(Debug, Default)]
+pub struct Incumbent {
+  best: Mutex<Option<HashSet<Edge>>>,
+}
+
+impl Incumbent {
+  pub fn new() -> Self {
+    Self {
+      best: Mutex::new(None),
+    }
+  }
+
+  /// Replaces the incumbent if the offered feedback arc set is smaller.
+  /// Returns true if the offered feedback arc set became the new incumbent.
+  pub fn offer(&self, fas: HashSet<Edge>) -> bool {
+    let mut best = self.best.lock().unwrap();
+    match best.as_ref() {
+      Some(current) if current.len() <= fas.len() => false,
+      _ => {
+        *best = Some(fas);
+        true
+      }
+    }
+  }
+
+  /// Returns a copy of the best feedback arc set found so far
+  pub fn best(&self) -> Option<HashSet<Edge>> {
+    self.best.lock().unwrap().clone()
+  }
+
+  /// Returns the size of the best feedback arc set found so far
+  pub fn best_size(&self) -> Option<usize> {
+    self.best.lock().unwrap().as_ref().map(|fas| fas.len())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::solver::incumbent::Incumbent;
+  use std::collections::HashSet;
+
+  #[test]
+  fn keeps_smallest_solution() {
+    let incumbent = Incumbent::new();
+    assert_eq!(incumbent.best(), None);
+
+    assert!(incumbent.offer(HashSet::from([(0, 1), (1, 2)])));
+    assert!(!incumbent.offer(HashSet::from([(2, 0), (1, 2)])));
+    assert!(incumbent.offer(HashSet::from([(2, 0)])));
+    assert!(!incumbent.offer(HashSet::from([(0, 1), (1, 2), (2, 0)])));
+
+    assert_eq!(incumbent.best(), Some(HashSet::from([(2, 0)])));
+    assert_eq!(incumbent.best_size(), Some(1));
+  }
+}
@@ -0,0 +1,4 @@
+pub mod anytime;
+pub mod incumbent;
+pub mod pace;
+pub mod portfolio;
@@ -0,0 +1,108 @@
+use crate::graph::hash_table::{Edge, VertexId};
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::io::{Result, Write};
+
+// Output format described here: https://pacechallenge.org/2022/tracks/#output-format
+// PACE 2022 asks for a directed feedback *vertex* set, i.e. one vertex per line.
+
+/// Writes one arc "u v" per line, sorted for reproducible output
+pub fn write_arcs<W: Write>(writer: &mut W, fas: &HashSet<Edge>) -> Result<()> {
+  for (source, destination) in fas.iter().collect::<BTreeSet<_>>() {
+    writeln!(writer, "{source} {destination}")?;
+  }
+  writer.flush()
+}
+
+/// Writes one vertex per line
+pub fn write_vertices<W: Write>(writer: &mut W, vertices: &[VertexId]) -> Result<()> {
+  for v in vertices {
+    writeln!(writer, "{v}")?;
+  }
+  writer.flush()
+}
+
+/// Turns a feedback arc set into a directed feedback vertex set by greedily covering its arcs:
+/// Every cycle contains an arc of the feedback arc set, so it also contains one of the chosen vertices.
+/// The vertex with most uncovered arcs (ties go to the smaller id) is chosen first. The degrees are
+/// kept in an ordered set and decremented as arcs are covered, which takes O(|F| log |F|).
+pub fn feedback_vertex_set(fas: &HashSet<Edge>) -> Vec<VertexId> {
+  let arcs = fas.iter().copied().collect::<Vec<_>>();
+  let mut incident = HashMap::<VertexId, Vec<usize>>::new();
+  for (i, (source, destination)) in arcs.iter().enumerate() {
+    incident.entry(*source).or_default().push(i);
+    incident.entry(*destination).or_default().push(i);
+  }
+  let mut degree = incident
+    .iter()
+    .map(|(v, arcs)| (*v, arcs.len()))
+    .collect::<HashMap<_, _>>();
+  let mut by_degree = degree
+    .iter()
+    .map(|(v, d)| (Reverse(*d), *v))
+    .collect::<BTreeSet<_>>();
+  let mut covered = vec![false; arcs.len()];
+  let mut dfvs = vec![];
+
+  while let Some((Reverse(d), v)) = by_degree.pop_first() {
+    if d == 0 {
+      break;
+    }
+    dfvs.push(v);
+    for &i in &incident[&v] {
+      if std::mem::replace(&mut covered[i], true) {
+        continue;
+      }
+      let (source, destination) = arcs[i];
+      let other = if source == v { destination } else { source };
+      if other != v {
+        let d = degree.get_mut(&other).unwrap();
+        by_degree.remove(&(Reverse(*d), other));
+        *d -= 1;
+        by_degree.insert((Reverse(*d), other));
+      }
+    }
+  }
+
+  dfvs.sort_unstable();
+  dfvs
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::solver::anytime::trivial_feedback_arc_set;
+  use crate::solver::pace::{feedback_vertex_set, write_arcs};
+  use crate::tools::graphs::graph_from_file;
+  use std::collections::HashSet;
+
+  #[test]
+  fn writes_sorted_arcs() {
+    let mut output = vec![];
+    write_arcs(&mut output, &HashSet::from([(3, 1), (1, 2)])).unwrap();
+
+    assert_eq!(String::from_utf8(output).unwrap(), "1 2\n3 1\n");
+  }
+
+  #[test]
+  fn covers_all_arcs_with_few_vertices() {
+    let fas = HashSet::from([(1, 2), (3, 2), (2, 4), (5, 6)]);
+    let dfvs = feedback_vertex_set(&fas);
+
+    assert_eq!(dfvs.len(), 2);
+    assert!(dfvs.contains(&2));
+    assert!(fas
+      .iter()
+      .all(|(source, destination)| dfvs.contains(source) || dfvs.contains(destination)));
+  }
+
+  #[test]
+  fn covers_trivial_solution_of_large_instance() {
+    let graph = graph_from_file("h_029");
+    let fas = trivial_feedback_arc_set(&graph);
+    let dfvs = feedback_vertex_set(&fas);
+
+    assert!(fas
+      .iter()
+      .all(|(source, destination)| dfvs.contains(source) || dfvs.contains(destination)));
+  }
+}
@@ -0,0 +1,277 @@
+use crate::fas::algorithm::Algorithm;
+use crate::fas::feedback_arc_set::FeedbackArcSet;
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use crate::scc::strongly_connected_components::cyclic_components;
+use crate::solver::anytime::trivial_feedback_arc_set;
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/*
+Portfolio of algorithms, because no single heuristic wins on all graphs:
+
+C := strongly connected components of G that contain a cycle     {Tarjan}
+jobs := (A1, C1), ..., (A1, Ck), (A2, C1), ..., (Am, Ck)          {algorithm by algorithm}
+worker threads take the next job until all are done or the time budget is used up,
+time-aware algorithms get the remaining budget as deadline
+for every component keep the smallest FAS, ties go to the earlier algorithm
+a component without any result gets the leftward arcs of its vertices ordered by id
+return the union of the FAS of all components
+
+Simulated annealing and tabu search stop at the deadline, all other algorithms run to completion
+once started (see Algorithm::feedback_arc_set_with_deadline). Putting a fast algorithm first makes
+sure every component has a result early.
+ */
+pub struct Portfolio<'a> {
+  pub graph: &'a HashTable,
+  pub algorithms: Vec<Algorithm>,
+  pub time_budget: Option<Duration>,
+  pub threads: usize,
+  /// Seed of all randomized algorithms
+  pub seed: u64,
+}
+
+/// Outcome of the portfolio on a single component
+#[derive(Clone, Debug)]
+pub struct ComponentResult {
+  /// The vertices of the component in increasing order
+  pub vertices: Vec<VertexId>,
+  pub vertex_count: usize,
+  pub arc_count: usize,
+  /// The size of the FAS of every algorithm that finished in time, in the order of the portfolio
+  pub sizes: Vec<(Algorithm, usize)>,
+  /// None if no algorithm finished in time
+  pub winner: Option<Algorithm>,
+  pub fas_size: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct PortfolioReport {
+  pub components: Vec<ComponentResult>,
+  pub wall_time: Duration,
+}
+
+impl<'a> Portfolio<'a> {
+  /// Runs the algorithms one thread per available core without time budget
+  pub fn new(graph: &'a HashTable, algorithms: Vec<Algorithm>) -> Self {
+    assert!(!algorithms.is_empty());
+    Self {
+      graph,
+      algorithms,
+      time_budget: None,
+      threads: thread::available_parallelism().map_or(1, |n| n.get()),
+      seed: 0,
+    }
+  }
+
+  pub fn with_time_budget(mut self, time_budget: Duration) -> Self {
+    self.time_budget = Some(time_budget);
+    self
+  }
+
+  pub fn with_threads(mut self, threads: usize) -> Self {
+    assert!(threads > 0);
+    self.threads = threads;
+    self
+  }
+
+  pub fn with_seed(mut self, seed: u64) -> Self {
+    self.seed = seed;
+    self
+  }
+
+  /// The feedback arc set and which algorithm won on which component
+  pub fn solve(&self) -> (HashSet<Edge>, PortfolioReport) {
+    let start = Instant::now();
+    let deadline = self.time_budget.map(|budget| start + budget);
+    let components = cyclic_components(self.graph);
+    let jobs = self
+      .algorithms
+      .iter()
+      .enumerate()
+      .flat_map(|(a, _)| (0..components.len()).map(move |c| (a, c)))
+      .collect::<Vec<_>>();
+
+    // results[c][a] is the FAS of algorithm a on component c
+    let results = Mutex::new(vec![vec![None; self.algorithms.len()]; components.len()]);
+    let next_job = AtomicUsize::new(0);
+    thread::scope(|scope| {
+      for _ in 0..self.threads.min(jobs.len()) {
+        scope.spawn(|| loop {
+          if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            break;
+          }
+          let Some(&(a, c)) = jobs.get(next_job.fetch_add(1, Ordering::Relaxed)) else {
+            break;
+          };
+          let fas =
+            self.algorithms[a].feedback_arc_set_with_deadline(&components[c], self.seed, deadline);
+          results.lock().unwrap()[c][a] = Some(fas);
+        });
+      }
+    });
+
+    let mut fas = HashSet::new();
+    let mut report = PortfolioReport {
+      components: vec![],
+      wall_time: Duration::ZERO,
+    };
+    for (component, results) in components.iter().zip(results.into_inner().unwrap()) {
+      let sizes = self
+        .algorithms
+        .iter()
+        .zip(&results)
+        .filter_map(|(algorithm, fas)| fas.as_ref().map(|fas| (*algorithm, fas.len())))
+        .collect::<Vec<_>>();
+      // min_by_key returns the first of several minima, i.e. the earlier algorithm
+      let best = self
+        .algorithms
+        .iter()
+        .zip(results)
+        .filter_map(|(algorithm, fas)| fas.map(|fas| (*algorithm, fas)))
+        .min_by_key(|(_, fas)| fas.len());
+      let (winner, component_fas) = match best {
+        Some((algorithm, fas)) => (Some(algorithm), fas),
+        None => (None, trivial_feedback_arc_set(component)),
+      };
+
+      report.components.push(ComponentResult {
+        vertices: component.vertices(),
+        vertex_count: component.order(),
+        arc_count: component.edge_count(),
+        sizes,
+        winner,
+        fas_size: component_fas.len(),
+      });
+      fas.extend(component_fas);
+    }
+    report.wall_time = start.elapsed();
+    (fas, report)
+  }
+}
+
+impl<'a> FeedbackArcSet for Portfolio<'a> {
+  fn feedback_arc_set(&self) -> HashSet<Edge> {
+    self.solve().0
+  }
+}
+
+impl PortfolioReport {
+  /// How many components every algorithm won, in the order of the first win
+  pub fn wins(&self) -> Vec<(Option<Algorithm>, usize)> {
+    let mut wins: Vec<(Option<Algorithm>, usize)> = vec![];
+    for component in &self.components {
+      match wins
+        .iter_mut()
+        .find(|(winner, _)| *winner == component.winner)
+      {
+        Some((_, count)) => *count += 1,
+        None => wins.push((component.winner, 1)),
+      }
+    }
+    wins
+  }
+}
+
+impl Display for PortfolioReport {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    let total = self.components.iter().map(|c| c.fas_size).sum::<usize>();
+    writeln!(
+      f,
+      "{} cyclic components, FAS size {total}, {:.3} s",
+      self.components.len(),
+      self.wall_time.as_secs_f64()
+    )?;
+    for (i, component) in self.components.iter().enumerate() {
+      let winner = component.winner.map_or("none (trivial)", |a| a.name());
+      let sizes = component
+        .sizes
+        .iter()
+        .map(|(algorithm, size)| format!("{algorithm} {size}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+      writeln!(
+        f,
+        "component {i} (vertex {}): {} vertices, {} arcs, winner {winner} with {} ({sizes})",
+        component.vertices[0], component.vertex_count, component.arc_count, component.fas_size
+      )?;
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::fas::algorithm::Algorithm;
+  use crate::graph::hash_table::HashTable;
+  use crate::solver::portfolio::Portfolio;
+  use crate::tools::graphs::{graph_from_file, graph_with_multiple_cliques};
+  use std::time::{Duration, Instant};
+
+  #[test]
+  fn keeps_best_result_per_component() {
+    let graph = graph_from_file("h_001");
+    let algorithms = vec![Algorithm::Greedy, Algorithm::PageRank];
+    let (fas, report) = Portfolio::new(&graph, algorithms).solve();
+
+    let mut acyclic_graph = graph.clone();
+    fas.iter().for_each(|e| acyclic_graph.remove_edge(*e));
+    assert!(!acyclic_graph.is_cyclic());
+    assert!(fas.len() <= Algorithm::PageRank.feedback_arc_set(&graph, 0).len());
+    for component in &report.components {
+      assert_eq!(component.sizes.len(), 2);
+      let best = component.sizes.iter().map(|(_, size)| *size).min();
+      assert_eq!(Some(component.fas_size), best);
+    }
+    assert_eq!(
+      report.wins().iter().map(|(_, count)| count).sum::<usize>(),
+      report.components.len()
+    );
+  }
+
+  #[test]
+  fn ignores_acyclic_parts() {
+    let graph = graph_with_multiple_cliques();
+    let (fas, report) = Portfolio::new(&graph, vec![Algorithm::Greedy])
+      .with_threads(2)
+      .solve();
+
+    assert_eq!(report.components.len(), 1);
+    assert_eq!(report.components[0].winner, Some(Algorithm::Greedy));
+    assert!(fas.len() <= Algorithm::Greedy.feedback_arc_set(&graph, 0).len());
+    assert!(report.to_string().contains("winner greedy"));
+  }
+
+  #[test]
+  fn falls_back_to_trivial_solution_without_time() {
+    let graph = HashTable::from_edges(&[(0, 1), (1, 0), (2, 3), (3, 2)]);
+    let portfolio =
+      Portfolio::new(&graph, vec![Algorithm::Greedy]).with_time_budget(Duration::ZERO);
+    let (fas, report) = portfolio.solve();
+
+    assert_eq!(fas.len(), 2);
+    assert!(report.components.iter().all(|c| c.winner.is_none()));
+    assert_eq!(report.components[0].vertices, vec![0, 1]);
+    assert_eq!(report.components[1].vertices, vec![2, 3]);
+  }
+
+  #[test]
+  fn passes_remaining_budget_to_time_aware_algorithms() {
+    let graph = graph_from_file("h_001");
+    let start = Instant::now();
+    let (_, report) = Portfolio::new(&graph, vec![Algorithm::SimulatedAnnealing])
+      .with_threads(1)
+      .with_time_budget(Duration::from_millis(200))
+      .solve();
+
+    // Without deadline simulated annealing runs for several seconds on h_001
+    assert!(start.elapsed() < Duration::from_secs(2));
+    assert!(report
+      .components
+      .iter()
+      .all(|c| c.winner == Some(Algorithm::SimulatedAnnealing)));
+  }
+}
@@ -1,5 +1,6 @@
 use crate::graph::hash_table::HashTable;
 use crate::tools::metis::Metis;
+use std::io::BufRead;
 
 pub fn graph_from_file(filename: &str) -> HashTable {
   let mut path = "test/resources/".to_owned();
@@ -16,6 +17,13 @@ pub fn graph_from_file(filename: &str) -> HashTable {
   HashTable::from_vertices_and_edges(parser.vertices(), parser.edges())
 }
 
+pub fn graph_from_reader<R: BufRead>(reader: R) -> HashTable {
+  let mut parser = Metis::new("-");
+  parser.parse_reader(reader);
+
+  HashTable::from_vertices_and_edges(parser.vertices(), parser.edges())
+}
+
 pub fn graph_with_multiple_cliques() -> HashTable {
   HashTable::from_edges(&[
     (0, 1),
@@ -0,0 +1,151 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+pub const BUNDLED_MANIFEST: &str = "test/resources/manifest.csv";
+
+/// What is known about the feedback arc set of an instance
+#[derive(Clone, Debug, PartialEq)]
+pub struct ManifestEntry {
+  pub instance: String,
+  pub vertex_count: usize,
+  pub arc_count: usize,
+  /// Size of the optimal or, if not proven, the best known feedback arc set
+  pub fas_size: usize,
+  pub is_optimal: bool,
+  /// Relative deviation from fas_size that a heuristic may have before it counts as regression
+  pub tolerance: f64,
+  pub source: String,
+}
+
+impl ManifestEntry {
+  /// The largest feedback arc set a heuristic may return without regression
+  pub fn max_accepted_fas_size(&self) -> usize {
+    (self.fas_size as f64 * (1.0 + self.tolerance)).floor() as usize
+  }
+}
+
+/*
+Machine-readable list of instances as CSV, lines starting with '#' are comments:
+instance,vertices,arcs,fas_size,optimal,tolerance,source
+ */
+#[derive(Clone, Debug, Default)]
+pub struct Manifest {
+  entries: BTreeMap<String, ManifestEntry>,
+}
+
+impl Manifest {
+  /// Loads the manifest of the instances in test/resources
+  pub fn bundled() -> io::Result<Manifest> {
+    Manifest::load(BUNDLED_MANIFEST)
+  }
+
+  pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Manifest> {
+    Manifest::parse(&fs::read_to_string(path)?)
+      .map_err(|message| io::Error::new(io::ErrorKind::InvalidData, message))
+  }
+
+  pub fn parse(content: &str) -> Result<Manifest, String> {
+    let mut lines = content
+      .lines()
+      .enumerate()
+      .filter(|(_, line)| !line.starts_with('#') && !line.trim().is_empty());
+
+    match lines.next() {
+      Some((_, "instance,vertices,arcs,fas_size,optimal,tolerance,source")) => (),
+      _ => return Err("Missing manifest header".to_string()),
+    }
+
+    let mut entries = BTreeMap::new();
+    for (idx, line) in lines {
+      let entry = parse_entry(line).map_err(|e| format!("Line {}: {e}", idx + 1))?;
+      entries.insert(entry.instance.clone(), entry);
+    }
+
+    Ok(Manifest { entries })
+  }
+
+  pub fn get(&self, instance: &str) -> Option<&ManifestEntry> {
+    self.entries.get(instance)
+  }
+
+  /// Adds or replaces the entry of an instance, e.g. of a generated one
+  pub fn insert(&mut self, entry: ManifestEntry) {
+    self.entries.insert(entry.instance.clone(), entry);
+  }
+
+  /// All entries sorted by instance name
+  pub fn entries(&self) -> impl Iterator<Item = &ManifestEntry> {
+    self.entries.values()
+  }
+}
+
+fn parse_entry(line: &str) -> Result<ManifestEntry, String> {
+  let fields = line.splitn(7, ',').collect::<Vec<_>>();
+  if fields.len() != 7 {
+    return Err(format!("Expected 7 fields, got {}", fields.len()));
+  }
+
+  let number = |idx: usize| {
+    fields[idx]
+      .parse::<usize>()
+      .map_err(|e| format!("Invalid number '{}': {e}", fields[idx]))
+  };
+
+  Ok(ManifestEntry {
+    instance: fields[0].to_string(),
+    vertex_count: number(1)?,
+    arc_count: number(2)?,
+    fas_size: number(3)?,
+    is_optimal: fields[4]
+      .parse::<bool>()
+      .map_err(|e| format!("Invalid flag '{}': {e}", fields[4]))?,
+    tolerance: fields[5]
+      .parse::<f64>()
+      .map_err(|e| format!("Invalid tolerance '{}': {e}", fields[5]))?,
+    source: fields[6].to_string(),
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::tools::graphs::graph_from_file;
+  use crate::tools::manifest::Manifest;
+
+  #[test]
+  fn parses_entries() {
+    let manifest = Manifest::parse(
+      "# comment\ninstance,vertices,arcs,fas_size,optimal,tolerance,source\nx_1,3,3,1,true,0.5,hand-made, triangle\n",
+    )
+    .unwrap();
+
+    let entry = manifest.get("x_1").unwrap();
+    assert_eq!(entry.vertex_count, 3);
+    assert_eq!(entry.fas_size, 1);
+    assert!(entry.is_optimal);
+    assert_eq!(entry.max_accepted_fas_size(), 1);
+    assert_eq!(entry.source, "hand-made, triangle");
+  }
+
+  #[test]
+  fn rejects_invalid_manifests() {
+    assert!(Manifest::parse("x_1,3,3,1,true,0.5,source").is_err());
+    assert!(Manifest::parse(
+      "instance,vertices,arcs,fas_size,optimal,tolerance,source\nx_1,3,three,1,true,0.5,source"
+    )
+    .is_err());
+  }
+
+  #[test]
+  fn bundled_manifest_matches_instances() {
+    let manifest = Manifest::bundled().unwrap();
+    assert_eq!(manifest.entries().count(), 18);
+
+    for entry in manifest.entries() {
+      let graph = graph_from_file(&entry.instance);
+      assert_eq!(graph.order(), entry.vertex_count, "{}", entry.instance);
+      assert_eq!(graph.edge_count(), entry.arc_count, "{}", entry.instance);
+    }
+  }
+}
@@ -1,6 +1,9 @@
+use crate::graph::hash_table::HashTable;
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
 use std::fs::File;
 use std::io;
-use std::io::BufRead;
+use std::io::{BufRead, Lines};
 
 // Input format described here: https://pacechallenge.org/2022/tracks/
 pub struct Metis {
@@ -31,14 +34,23 @@ impl Metis {
 
   pub fn parse(&mut self) {
     if let Ok(lines) = self.lines() {
-      let mut idx = 0;
-      for line in lines.flatten() {
-        if idx != 0 {
-          idx = self.parse_content_line(line, idx);
-        } else {
-          self.parse_header_line(line);
-          idx += 1;
-        }
+      self.parse_lines(lines);
+    }
+  }
+
+  // Parses the graph from any reader, e.g. stdin as required by the PACE tracks.
+  pub fn parse_reader<R: BufRead>(&mut self, reader: R) {
+    self.parse_lines(reader.lines());
+  }
+
+  fn parse_lines<R: BufRead>(&mut self, lines: Lines<R>) {
+    let mut idx = 0;
+    for line in lines.map_while(Result::ok) {
+      if idx != 0 {
+        idx = self.parse_content_line(line, idx);
+      } else {
+        self.parse_header_line(line);
+        idx += 1;
       }
     }
   }
@@ -77,10 +89,51 @@ impl Metis {
   }
 }
 
+/// Writes a graph in METIS format. The vertices are renumbered 1..n in the order of their ids.
+pub struct MetisWriter<'a> {
+  graph: &'a HashTable,
+}
+
+impl<'a> MetisWriter<'a> {
+  pub fn new(graph: &'a HashTable) -> Self {
+    Self { graph }
+  }
+}
+
+impl<'a> Display for MetisWriter<'a> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+    let vertices = self.graph.vertices();
+    let index = vertices
+      .iter()
+      .enumerate()
+      .map(|(i, v)| (*v, i + 1))
+      .collect::<HashMap<_, _>>();
+
+    writeln!(f, "{} {} 0", vertices.len(), self.graph.edge_count())?;
+    for v in &vertices {
+      let mut successors = self
+        .graph
+        .neighborhood(v)
+        .iter()
+        .map(|u| index[u])
+        .collect::<Vec<_>>();
+      successors.sort_unstable();
+      let line = successors
+        .iter()
+        .map(|u| u.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+      writeln!(f, "{line}")?;
+    }
+    Ok(())
+  }
+}
+
 #[cfg(test)]
 mod tests {
-  use crate::tools::graphs::graph_from_file;
-  use crate::tools::metis::Metis;
+  use crate::graph::hash_table::HashTable;
+  use crate::tools::graphs::{graph_from_file, graph_from_reader};
+  use crate::tools::metis::{Metis, MetisWriter};
 
   #[test]
   fn can_parse_e_001() {
@@ -101,6 +154,30 @@ mod tests {
     assert_eq!(e_001.edges.len(), e_001.expected_edge_count);
   }
 
+  #[test]
+  fn can_parse_from_reader() {
+    let mut parser = Metis::new("-");
+    parser.parse_reader("3 3 0\n2\n3\n1\n".as_bytes());
+
+    assert_eq!(parser.vertices(), &[1, 2, 3]);
+    assert_eq!(parser.edges(), &[(1, 2), (2, 3), (3, 1)]);
+  }
+
+  #[test]
+  fn writes_what_it_reads() {
+    let graph = graph_from_file("h_001");
+    let written = graph_from_reader(MetisWriter::new(&graph).to_string().as_bytes());
+
+    assert_eq!(written.all_edges(), graph.all_edges());
+  }
+
+  #[test]
+  fn renumbers_vertices_from_one() {
+    let graph = HashTable::from_vertices_and_edges(&[0, 1, 5], &[(5, 0), (0, 1), (0, 5)]);
+
+    assert_eq!(MetisWriter::new(&graph).to_string(), "3 3 0\n2 3\n\n1\n");
+  }
+
   #[test]
   fn can_load_graph_from_file() {
     let cyclic_graph = graph_from_file("h_001");
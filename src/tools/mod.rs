@@ -1,4 +1,7 @@
 pub mod cycle;
 pub mod dot;
 pub mod graphs;
+pub mod manifest;
 pub mod metis;
+pub mod quality;
+pub mod svg;
@@ -0,0 +1,342 @@
+use crate::fas::algorithm::Algorithm;
+use crate::fas::lower_bound::cycle_packing_bound;
+use crate::fas::solution::ratio;
+use crate::graph::hash_table::HashTable;
+use crate::tools::graphs::graph_from_reader;
+use crate::tools::manifest::Manifest;
+use std::collections::BTreeMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::Path;
+use std::time::Duration;
+
+pub const BUNDLED_INSTANCE_DIRECTORIES: [&str; 2] =
+  ["test/resources/heuristic", "test/resources/exact"];
+
+/// A named graph on which the algorithms are compared
+pub struct Instance {
+  pub name: String,
+  pub graph: HashTable,
+}
+
+/// Loads all h_* and e_* instances shipped with the repository
+pub fn bundled_instances() -> io::Result<Vec<Instance>> {
+  let mut instances = vec![];
+  for directory in BUNDLED_INSTANCE_DIRECTORIES {
+    instances.extend(instances_from_directory(directory)?);
+  }
+  Ok(instances)
+}
+
+/// Loads every file of the directory as METIS graph, sorted by file name
+pub fn instances_from_directory<P: AsRef<Path>>(directory: P) -> io::Result<Vec<Instance>> {
+  let mut paths = fs::read_dir(directory)?
+    .map(|entry| entry.map(|e| e.path()))
+    .collect::<io::Result<Vec<_>>>()?;
+  paths.retain(|path| path.is_file());
+  paths.sort();
+
+  paths
+    .into_iter()
+    .map(|path| {
+      let graph = graph_from_reader(BufReader::new(File::open(&path)?));
+      let name = path.file_name().unwrap().to_string_lossy().into_owned();
+      Ok(Instance { name, graph })
+    })
+    .collect()
+}
+
+/// The result of a single run of an algorithm on an instance
+#[derive(Clone, Debug)]
+pub struct QualityRecord {
+  pub instance: String,
+  pub vertex_count: usize,
+  pub arc_count: usize,
+  pub algorithm: Algorithm,
+  pub seed: u64,
+  pub fas_size: usize,
+  pub runtime: Duration,
+  /// The smallest feedback arc set known for the instance, from the manifest or this comparison
+  pub best_known: usize,
+  /// No FAS of the instance is smaller, certified by a packing of arc-disjoint cycles
+  pub lower_bound: usize,
+}
+
+impl QualityRecord {
+  /// Relative distance to the best known feedback arc set, e.g. 0.1 if the FAS is 10% larger
+  pub fn gap(&self) -> f64 {
+    if self.best_known == 0 {
+      0.0
+    } else {
+      (self.fas_size as f64 - self.best_known as f64) / self.best_known as f64
+    }
+  }
+
+  /// fas_size / lower_bound, 1.0 proves that the FAS is optimal
+  pub fn ratio(&self) -> f64 {
+    ratio(self.fas_size, self.lower_bound)
+  }
+}
+
+/*
+Runs every algorithm on every instance and records the size of the FAS instead of only the runtime.
+Randomized algorithms are run with the seeds 0..seeds, deterministic ones only once.
+ */
+pub struct QualityHarness {
+  algorithms: Vec<Algorithm>,
+  seeds: u64,
+  manifest: Manifest,
+}
+
+impl QualityHarness {
+  pub fn new(algorithms: Vec<Algorithm>, seeds: u64) -> Self {
+    assert!(seeds > 0);
+    Self {
+      algorithms,
+      seeds,
+      manifest: Manifest::default(),
+    }
+  }
+
+  /// Uses the best known FAS sizes of the manifest to compute the gaps
+  pub fn with_manifest(mut self, manifest: Manifest) -> Self {
+    self.manifest = manifest;
+    self
+  }
+
+  pub fn run(&self, instances: &[Instance]) -> QualityReport {
+    let mut records = vec![];
+
+    for instance in instances {
+      let first_record = records.len();
+      let lower_bound = cycle_packing_bound(&instance.graph).bound;
+      for algorithm in &self.algorithms {
+        let seeds = if algorithm.is_randomized() {
+          self.seeds
+        } else {
+          1
+        };
+        for seed in 0..seeds {
+          let solution = algorithm.solve(&instance.graph, seed);
+
+          records.push(QualityRecord {
+            instance: instance.name.clone(),
+            vertex_count: instance.graph.order(),
+            arc_count: instance.graph.edge_count(),
+            algorithm: *algorithm,
+            seed,
+            fas_size: solution.size(),
+            runtime: solution.wall_time,
+            best_known: usize::MAX,
+            lower_bound,
+          });
+        }
+      }
+
+      // Without an entry in the manifest, the best solution of this comparison is the best known one
+      let best_known = records[first_record..]
+        .iter()
+        .map(|record| record.fas_size)
+        .chain(
+          self
+            .manifest
+            .get(&instance.name)
+            .map(|entry| entry.fas_size),
+        )
+        .min()
+        .unwrap_or_default();
+      records[first_record..]
+        .iter_mut()
+        .for_each(|record| record.best_known = best_known);
+    }
+
+    QualityReport { records }
+  }
+}
+
+pub struct QualityReport {
+  pub records: Vec<QualityRecord>,
+}
+
+impl QualityReport {
+  /// One line per run, i.e. per instance, algorithm and seed
+  pub fn to_csv(&self) -> String {
+    let mut csv =
+      "instance,vertices,arcs,algorithm,seed,fas_size,runtime_ms,best_known,gap,lower_bound,ratio\n".to_string();
+    for r in &self.records {
+      csv += &format!(
+        "{},{},{},{},{},{},{:.3},{},{:.4},{},{:.4}\n",
+        r.instance,
+        r.vertex_count,
+        r.arc_count,
+        r.algorithm,
+        r.seed,
+        r.fas_size,
+        r.runtime.as_secs_f64() * 1000.0,
+        r.best_known,
+        r.gap(),
+        r.lower_bound,
+        r.ratio()
+      );
+    }
+    csv
+  }
+
+  /// One row per instance and one column per algorithm, showing the best FAS size / lower bound, its gap and the mean runtime
+  pub fn to_markdown(&self) -> String {
+    let mut algorithms = vec![];
+    for record in &self.records {
+      if !algorithms.contains(&record.algorithm) {
+        algorithms.push(record.algorithm);
+      }
+    }
+
+    let mut by_instance = BTreeMap::<&str, Vec<&QualityRecord>>::new();
+    for record in &self.records {
+      by_instance
+        .entry(&record.instance)
+        .or_default()
+        .push(record);
+    }
+
+    let mut markdown = "| Instance | n | m | Lower bound | Best known |".to_string();
+    for algorithm in &algorithms {
+      markdown += &format!(" {algorithm} |");
+    }
+    markdown += "\n|---|---:|---:|---:|---:|";
+    markdown += &"---:|".repeat(algorithms.len());
+    markdown += "\n";
+
+    for (instance, records) in by_instance {
+      let first = records[0];
+      markdown += &format!(
+        "| {} | {} | {} | {} | {} |",
+        instance, first.vertex_count, first.arc_count, first.lower_bound, first.best_known
+      );
+      for algorithm in &algorithms {
+        let runs = records
+          .iter()
+          .filter(|r| r.algorithm == *algorithm)
+          .collect::<Vec<_>>();
+        match runs.iter().min_by_key(|r| r.fas_size) {
+          Some(best) => {
+            let mean_runtime = runs.iter().map(|r| r.runtime).sum::<Duration>() / runs.len() as u32;
+            markdown += &format!(
+              " {} / {} = {:.2} (+{:.1}%, {:.1} ms) |",
+              best.fas_size,
+              best.lower_bound,
+              best.ratio(),
+              best.gap() * 100.0,
+              mean_runtime.as_secs_f64() * 1000.0
+            );
+          }
+          None => markdown += " - |",
+        }
+      }
+      markdown += "\n";
+    }
+    markdown
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::fas::algorithm::Algorithm;
+  use crate::generator::planted::PlantedFas;
+  use crate::tools::graphs::{graph_from_file, graph_from_wikipedia_scc};
+  use crate::tools::manifest::Manifest;
+  use crate::tools::quality::{bundled_instances, Instance, QualityHarness};
+  use rand::rngs::StdRng;
+  use rand::SeedableRng;
+
+  fn instances() -> Vec<Instance> {
+    vec![
+      Instance {
+        name: "wikipedia".to_string(),
+        graph: graph_from_wikipedia_scc(),
+      },
+      Instance {
+        name: "h_001".to_string(),
+        graph: graph_from_file("h_001"),
+      },
+    ]
+  }
+
+  #[test]
+  fn loads_bundled_instances() {
+    let instances = bundled_instances().unwrap();
+
+    assert_eq!(instances.len(), 18);
+    assert_eq!(instances[0].name, "h_001");
+    assert_eq!(instances[0].graph.edge_count(), 2103);
+  }
+
+  #[test]
+  fn runs_randomized_algorithms_several_times() {
+    let harness = QualityHarness::new(vec![Algorithm::Simple, Algorithm::Greedy], 3);
+    let report = harness.run(&instances());
+
+    assert_eq!(report.records.len(), 2 * (3 + 1));
+    for record in &report.records {
+      assert!(record.fas_size >= record.best_known);
+      assert!(record.gap() >= 0.0);
+      assert!(record.lower_bound <= record.fas_size && record.ratio() >= 1.0);
+    }
+    assert!(report
+      .records
+      .iter()
+      .filter(|r| r.instance == "h_001")
+      .any(|r| r.gap() == 0.0));
+  }
+
+  #[test]
+  fn uses_best_known_of_manifest() {
+    let manifest = Manifest::parse(
+      "instance,vertices,arcs,fas_size,optimal,tolerance,source\nh_001,1024,2103,1,false,0.0,made up\n",
+    )
+    .unwrap();
+    let harness = QualityHarness::new(vec![Algorithm::Greedy], 1).with_manifest(manifest);
+    let report = harness.run(&instances());
+
+    assert_eq!(report.records[0].best_known, report.records[0].fas_size);
+    assert_eq!(report.records[1].best_known, 1);
+    assert!(report.records[1].gap() > 1.0);
+  }
+
+  #[test]
+  fn measures_gap_to_planted_optimum() {
+    let instance = PlantedFas::new(300, 0.02, 40).generate(&mut StdRng::seed_from_u64(0));
+    let mut manifest = Manifest::default();
+    manifest.insert(instance.manifest_entry("planted"));
+    let harness = QualityHarness::new(vec![Algorithm::Greedy], 1).with_manifest(manifest);
+    let report = harness.run(&[Instance {
+      name: "planted".to_string(),
+      graph: instance.graph,
+    }]);
+
+    assert_eq!(report.records[0].best_known, 40);
+    assert!(report.records[0].gap() >= 0.0);
+  }
+
+  #[test]
+  fn writes_csv_and_markdown() {
+    let harness = QualityHarness::new(vec![Algorithm::Greedy, Algorithm::Simple], 1);
+    let report = harness.run(&instances());
+
+    let csv = report.to_csv();
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 5);
+    assert!(lines[1].starts_with("wikipedia,8,14,greedy,0,"));
+
+    let markdown = report.to_markdown();
+    let rows = markdown.lines().collect::<Vec<_>>();
+    assert_eq!(rows.len(), 4);
+    assert_eq!(
+      rows[0],
+      "| Instance | n | m | Lower bound | Best known | greedy | simple |"
+    );
+    assert!(rows[2].starts_with("| h_001 | 1024 | 2103 |"));
+    assert!(rows[3].starts_with("| wikipedia | 8 | 14 | "));
+    assert!(rows[3].contains(" / "));
+  }
+}
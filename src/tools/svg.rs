@@ -0,0 +1,223 @@
+use crate::graph::hash_table::{Edge, HashTable, VertexId};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter, Result};
+
+/*
+Arc diagram of a vertex ordering as SVG:
+
+place the vertices on a horizontal line in the order of the ordering
+for every arc u → v
+    if u is left of v then draw a black half circle from u to v above the line
+    else draw a red half circle from u to v below the line   {u → v is in the FAS of the ordering}
+
+Both cases are the same clockwise SVG arc from u to v, so the direction of an arc can be read
+from its side. Self-loops are drawn as small red circles below their vertex.
+ */
+#[derive(Clone, Debug)]
+pub struct ArcDiagramOptions {
+  /// Writes the vertex ids into the vertices
+  pub labels: bool,
+  /// Weight of the arcs, arcs without a weight weigh 1. Heavier arcs are drawn thicker.
+  pub weights: BTreeMap<Edge, u64>,
+  /// Upper bound for the width of the image in pixels, the vertices move closer together
+  pub max_width: f64,
+}
+
+impl Default for ArcDiagramOptions {
+  fn default() -> Self {
+    Self {
+      labels: true,
+      weights: BTreeMap::new(),
+      max_width: 1200.0,
+    }
+  }
+}
+
+pub struct ArcDiagram<'a> {
+  graph: &'a HashTable,
+  ordering: &'a [VertexId],
+  options: ArcDiagramOptions,
+}
+
+const MARGIN: f64 = 20.0;
+const MAX_SPACING: f64 = 40.0;
+const MAX_STROKE_WIDTH: f64 = 4.0;
+
+impl<'a> ArcDiagram<'a> {
+  /// The ordering has to contain every vertex of the graph exactly once
+  pub fn new(graph: &'a HashTable, ordering: &'a [VertexId]) -> Self {
+    Self::with_options(graph, ordering, ArcDiagramOptions::default())
+  }
+
+  pub fn with_options(
+    graph: &'a HashTable,
+    ordering: &'a [VertexId],
+    options: ArcDiagramOptions,
+  ) -> Self {
+    assert_eq!(
+      ordering.len(),
+      graph.order(),
+      "Ordering needs every vertex once"
+    );
+    Self {
+      graph,
+      ordering,
+      options,
+    }
+  }
+
+  fn spacing(&self) -> f64 {
+    if self.ordering.len() < 2 {
+      return MAX_SPACING;
+    }
+    let available = (self.options.max_width - 2.0 * MARGIN).max(0.0);
+    MAX_SPACING.min(available / (self.ordering.len() - 1) as f64)
+  }
+
+  fn stroke_width(&self, e: &Edge, max_weight: u64) -> f64 {
+    let weight = self.options.weights.get(e).copied().unwrap_or(1);
+    if max_weight <= 1 {
+      1.0
+    } else {
+      1.0 + (MAX_STROKE_WIDTH - 1.0) * weight.saturating_sub(1) as f64 / (max_weight - 1) as f64
+    }
+  }
+}
+
+impl<'a> Display for ArcDiagram<'a> {
+  fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+    let position = self
+      .ordering
+      .iter()
+      .enumerate()
+      .map(|(p, v)| (*v, p))
+      .collect::<HashMap<_, _>>();
+    let spacing = self.spacing();
+    let x = |v: &VertexId| MARGIN + position[v] as f64 * spacing;
+    let radius: f64 = if self.options.labels { 8.0 } else { 3.0 };
+
+    let edges = self.graph.all_edges();
+    let max_weight = edges
+      .iter()
+      .map(|e| self.options.weights.get(e).copied().unwrap_or(1))
+      .max()
+      .unwrap_or(1);
+    // The largest half circles above and below the line
+    let (mut above, mut below) = (radius, 2.0 * radius + 4.0);
+    for (u, v) in &edges {
+      let half_span = (x(u) - x(v)).abs() / 2.0;
+      if position[u] < position[v] {
+        above = above.max(half_span);
+      } else {
+        below = below.max(half_span);
+      }
+    }
+    let line = MARGIN + above;
+    let width = 2.0 * MARGIN + spacing * self.ordering.len().saturating_sub(1) as f64;
+    let height = line + below + MARGIN;
+
+    writeln!(
+      f,
+      r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width:.1}" height="{height:.1}" viewBox="0 0 {width:.1} {height:.1}">"#
+    )?;
+    writeln!(f, r#"<g fill="none">"#)?;
+    for e @ (u, v) in &edges {
+      let stroke_width = self.stroke_width(e, max_weight);
+      if u == v {
+        writeln!(
+          f,
+          r#"<circle class="leftward" cx="{:.1}" cy="{:.1}" r="{:.1}" stroke="red" stroke-width="{stroke_width:.1}"/>"#,
+          x(u),
+          line + 2.0 * radius,
+          radius
+        )?;
+        continue;
+      }
+      let (class, colour) = if position[u] < position[v] {
+        ("forward", "black")
+      } else {
+        ("leftward", "red")
+      };
+      let r = (x(u) - x(v)).abs() / 2.0;
+      writeln!(
+        f,
+        r#"<path class="{class}" d="M {:.1} {line:.1} A {r:.1} {r:.1} 0 0 1 {:.1} {line:.1}" stroke="{colour}" stroke-width="{stroke_width:.1}"/>"#,
+        x(u),
+        x(v)
+      )?;
+    }
+    writeln!(f, "</g>")?;
+
+    for v in self.ordering {
+      writeln!(
+        f,
+        r#"<circle cx="{:.1}" cy="{line:.1}" r="{radius:.1}" fill="white" stroke="black"/>"#,
+        x(v)
+      )?;
+      if self.options.labels {
+        writeln!(
+          f,
+          r#"<text x="{:.1}" y="{:.1}" font-family="sans-serif" font-size="9" text-anchor="middle">{v}</text>"#,
+          x(v),
+          line + 3.0
+        )?;
+      }
+    }
+    writeln!(f, "</svg>")
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::fas::greedy::GreedyHeuristic;
+  use crate::graph::hash_table::HashTable;
+  use crate::ordering::topological_sort::leftward_edges;
+  use crate::ordering::vertex_ordering::VertexOrdering;
+  use crate::tools::graphs::graph_from_wikipedia_scc;
+  use crate::tools::svg::{ArcDiagram, ArcDiagramOptions};
+  use std::collections::BTreeMap;
+
+  #[test]
+  fn draws_leftward_arcs_in_red() {
+    let graph = graph_from_wikipedia_scc();
+    let ordering = GreedyHeuristic::new(&graph).vertex_ordering();
+    let svg = ArcDiagram::new(&graph, &ordering).to_string();
+
+    assert!(svg.starts_with("<svg") && svg.ends_with("</svg>\n"));
+    assert_eq!(
+      svg.matches(r#"class="leftward""#).count(),
+      leftward_edges(&graph, ordering.clone()).len()
+    );
+    assert_eq!(svg.matches("class=").count(), graph.edge_count());
+    assert_eq!(svg.matches("<text").count(), graph.order());
+  }
+
+  #[test]
+  fn applies_options() {
+    let graph = HashTable::from_edges(&[(0, 1), (1, 2), (2, 0), (1, 1)]);
+    let ordering = [0, 1, 2];
+    let options = ArcDiagramOptions {
+      labels: false,
+      weights: BTreeMap::from([((2, 0), 3)]),
+      max_width: 60.0,
+    };
+    let svg = ArcDiagram::with_options(&graph, &ordering, options).to_string();
+
+    assert!(svg.contains(r#"width="60.0""#));
+    assert!(!svg.contains("<text"));
+    assert!(svg.contains(r#"d="M 40.0 "#) && svg.contains(r#"stroke-width="4.0""#));
+    assert!(svg.contains(r#"<circle class="leftward""#));
+  }
+
+  #[test]
+  fn draws_arcs_of_weight_zero_thinnest() {
+    let graph = HashTable::from_edges(&[(0, 1), (1, 0)]);
+    let options = ArcDiagramOptions {
+      weights: BTreeMap::from([((0, 1), 0), ((1, 0), 2)]),
+      ..Default::default()
+    };
+    let svg = ArcDiagram::with_options(&graph, &[0, 1], options).to_string();
+
+    assert!(svg.contains(r#"stroke-width="1.0""#) && svg.contains(r#"stroke-width="4.0""#));
+  }
+}